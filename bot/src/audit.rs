@@ -0,0 +1,383 @@
+use std::collections::BTreeMap;
+
+use mysql::Pool;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    db::{
+        self,
+        audit::{AuditChange, MAX_VALUE_LENGTH},
+    },
+    printer, Error,
+};
+
+/// Identifies a value: the item it belongs to, the field and what tells several values of a field apart
+type Key = (Option<u64>, &'static str, String);
+
+/// The state a command is allowed to change, taken before and after it runs.
+/// A value missing from the snapshot is unset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<Key, String>);
+
+type Part = fn(&Pool, u64, &mut Snapshot) -> Result<(), Error>;
+
+/// Returns what a command can change, or none if it is not audited
+fn scope(command: &str) -> Option<&'static [Part]> {
+    let parts: &'static [Part] = match command {
+        "add" | "add_multiple" | "add_collection" | "remove" | "remove_multiple"
+        | "remove_collection" | "remove_all" | "undo_remove" => &[items],
+        "add_note" | "remove_note" => &[notes],
+        "mute" | "unmute" => &[muted],
+        "set_check_interval" => &[check_intervals],
+        "mark_deployed" => &[deployed],
+        "create_group" | "delete_group" | "group_add" | "group_remove" => &[groups],
+        "set_mention" => &[mentions, groups],
+        "set_schedule"
+        | "register_channel"
+        | "permissions"
+        | "set_webhook"
+        | "set_hook"
+        | "set_template"
+        | "set_timezone"
+        | "set_language"
+        | "set_notification_mode"
+        | "set_escalation" => &[settings],
+        "setup" => &[settings, mentions],
+        _ => return None,
+    };
+
+    Some(parts)
+}
+
+impl Snapshot {
+    fn set(&mut self, item_id: Option<u64>, field: &'static str, value: impl Into<Option<String>>) {
+        self.set_nth(item_id, field, String::new(), value);
+    }
+
+    fn set_nth(
+        &mut self,
+        item_id: Option<u64>,
+        field: &'static str,
+        nth: String,
+        value: impl Into<Option<String>>,
+    ) {
+        if let Some(value) = value.into() {
+            self.0.insert((item_id, field, nth), value);
+        }
+    }
+}
+
+fn items(pool: &Pool, guild_id: u64, s: &mut Snapshot) -> Result<(), Error> {
+    for (_, item, _) in db::subscriptions::get_all_subscriptions_of_guild(pool, guild_id)? {
+        s.set(Some(item.id), "tracked", "yes".to_string());
+    }
+    Ok(())
+}
+
+/// Every note on its own, the subscriptions only keep the latest ones joined together
+fn notes(pool: &Pool, guild_id: u64, s: &mut Snapshot) -> Result<(), Error> {
+    for (item_id, note_id, content) in db::notes::get_active_notes(pool, guild_id)? {
+        s.set_nth(Some(item_id), "note", note_id.to_string(), content);
+    }
+    Ok(())
+}
+
+fn muted(pool: &Pool, guild_id: u64, s: &mut Snapshot) -> Result<(), Error> {
+    for (item_id, until) in db::subscriptions::get_muted_items(pool, guild_id)? {
+        let value = match until {
+            Some(until) => format!("until <t:{}:f>", until),
+            None => "yes".to_string(),
+        };
+        s.set(Some(item_id), "muted", value);
+    }
+    Ok(())
+}
+
+fn check_intervals(pool: &Pool, guild_id: u64, s: &mut Snapshot) -> Result<(), Error> {
+    for (item_id, minutes) in db::subscriptions::get_check_intervals(pool, guild_id)? {
+        s.set(Some(item_id), "check interval", format!("{} min", minutes));
+    }
+    Ok(())
+}
+
+fn deployed(pool: &Pool, guild_id: u64, s: &mut Snapshot) -> Result<(), Error> {
+    for (item_id, version) in db::subscriptions::get_deployed_versions(pool, guild_id)? {
+        s.set(
+            Some(item_id),
+            "deployed version",
+            format!("<t:{}:f>", version),
+        );
+    }
+    Ok(())
+}
+
+fn groups(pool: &Pool, guild_id: u64, s: &mut Snapshot) -> Result<(), Error> {
+    for (name, mentions) in db::groups::get_group_settings(pool, guild_id)? {
+        s.set_nth(None, "group", name.clone(), name.clone());
+        s.set_nth(
+            None,
+            "group mention",
+            name.clone(),
+            join_mentions(&name, &mentions),
+        );
+    }
+
+    for (item_id, names) in db::groups::get_item_groups(pool, guild_id)? {
+        for name in names {
+            s.set_nth(Some(item_id), "group", name.clone(), name);
+        }
+    }
+    Ok(())
+}
+
+fn mentions(pool: &Pool, guild_id: u64, s: &mut Snapshot) -> Result<(), Error> {
+    let mentions = db::servers::get_mentions(pool, guild_id)?;
+    s.set(None, "mention", join_mentions("", &mentions));
+
+    let mut item_mentions: BTreeMap<u64, Vec<db::Mention>> = BTreeMap::new();
+    for (item_id, mention) in db::subscriptions::get_item_mentions(pool, guild_id)? {
+        item_mentions.entry(item_id).or_default().push(mention);
+    }
+
+    for (item_id, mentions) in item_mentions {
+        s.set(Some(item_id), "mention", join_mentions("", &mentions));
+    }
+    Ok(())
+}
+
+fn settings(pool: &Pool, guild_id: u64, s: &mut Snapshot) -> Result<(), Error> {
+    let (manager, contributor) = db::servers::get_permission_roles(pool, guild_id)?;
+    let templates = db::servers::get_templates(pool, guild_id)?;
+    let (hour, day) = db::servers::get_digest_time(pool, guild_id)?;
+
+    s.set(
+        None,
+        "channel",
+        db::servers::get_update_channel(pool, guild_id)?.map(|c| format!("<#{}>", c)),
+    );
+    s.set(
+        None,
+        "schedule",
+        db::servers::get_schedule(pool, guild_id)?.map(|s| format!("{}h", s)),
+    );
+    s.set(None, "manager role", manager.map(|r| format!("<@&{}>", r)));
+    s.set(
+        None,
+        "contributor role",
+        contributor.map(|r| format!("<@&{}>", r)),
+    );
+    // Urls of webhooks and hooks carry their credentials, so only a fingerprint is kept
+    s.set(
+        None,
+        "webhook",
+        db::servers::get_webhook(pool, guild_id)?.map(|(url, name, avatar_url)| {
+            let mut value = fingerprint(&url);
+            if let Some(name) = name {
+                value.push_str(&format!(", {}", name));
+            }
+            if let Some(avatar_url) = avatar_url {
+                value.push_str(&format!(", {}", avatar_url));
+            }
+            value
+        }),
+    );
+    s.set(
+        None,
+        "hook",
+        db::servers::get_hook(pool, guild_id)?
+            .map(|(url, secret)| format!("{}, {}", fingerprint(&url), fingerprint(&secret))),
+    );
+    s.set(None, "header template", templates.header);
+    s.set(None, "item template", templates.item);
+    s.set(
+        None,
+        "timezone",
+        db::servers::get_timezone(pool, guild_id)?
+            .name()
+            .to_string(),
+    );
+    s.set(
+        None,
+        "language",
+        db::servers::get_language(pool, guild_id)?
+            .as_str()
+            .to_string(),
+    );
+
+    let mode = db::servers::get_notification_mode(pool, guild_id)?;
+    let mode = match (hour, day) {
+        (Some(hour), Some(day)) => format!("{}, day {} at {}:00", mode.as_str(), day, hour),
+        (Some(hour), None) => format!("{} at {}:00", mode.as_str(), hour),
+        _ => mode.as_str().to_string(),
+    };
+    s.set(None, "notification mode", mode);
+
+    s.set(
+        None,
+        "escalation",
+        db::events::get_escalation_rule(pool, guild_id)?
+            .map(|(hours, role)| format!("{}h <@&{}>", hours, role)),
+    );
+    Ok(())
+}
+
+/// A short hash to tell secrets apart without revealing them
+fn fingerprint(secret: &str) -> String {
+    let hash = hex::encode(Sha256::digest(secret.as_bytes()));
+    format!("#{}", &hash[..8])
+}
+
+fn join_mentions(prefix: &str, mentions: &[db::Mention]) -> Option<String> {
+    if mentions.is_empty() {
+        return None;
+    }
+
+    let mentions: Vec<String> = mentions.iter().map(|m| m.to_string()).collect();
+
+    Some(
+        format!("{} {}", prefix, mentions.join(" "))
+            .trim()
+            .to_string(),
+    )
+}
+
+/// Takes the snapshot a command is compared against once it finished
+pub fn snapshot(pool: &Pool, guild_id: u64, command: &str) -> Result<Option<Snapshot>, Error> {
+    let parts = match scope(command) {
+        Some(parts) => parts,
+        None => return Ok(None),
+    };
+
+    let mut snapshot = Snapshot::default();
+    for part in parts {
+        part(pool, guild_id, &mut snapshot)?;
+    }
+
+    Ok(Some(snapshot))
+}
+
+/// Records everything a command changed since the snapshot was taken
+pub fn record(
+    pool: &Pool,
+    guild_id: u64,
+    user_id: u64,
+    command: &str,
+    before: &Snapshot,
+) -> Result<(), Error> {
+    let after = match snapshot(pool, guild_id, command)? {
+        Some(after) => after,
+        None => return Ok(()),
+    };
+
+    let changes = diff(before, &after);
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    db::audit::add_entries(pool, guild_id, user_id, command, &changes)
+}
+
+fn diff(before: &Snapshot, after: &Snapshot) -> Vec<AuditChange> {
+    let fit = |v: Option<&String>| v.map(|v| printer::truncate(v, MAX_VALUE_LENGTH));
+
+    let mut keys: Vec<&Key> = before.0.keys().chain(after.0.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter(|key| before.0.get(*key) != after.0.get(*key))
+        .map(|key| {
+            let (item_id, field, _) = key;
+            AuditChange {
+                item_id: *item_id,
+                field,
+                old_value: fit(before.0.get(key)),
+                new_value: fit(after.0.get(key)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_records_added_removed_and_changed_values() {
+        let mut before = Snapshot::default();
+        before.set(Some(1), "tracked", "yes".to_string());
+        before.set(Some(2), "tracked", "yes".to_string());
+        before.set(None, "channel", "<#1>".to_string());
+
+        let mut after = Snapshot::default();
+        after.set(Some(2), "tracked", "yes".to_string());
+        after.set(Some(3), "tracked", "yes".to_string());
+        after.set(None, "channel", "<#2>".to_string());
+
+        assert_eq!(
+            diff(&before, &after),
+            vec![
+                AuditChange {
+                    item_id: None,
+                    field: "channel",
+                    old_value: Some("<#1>".to_string()),
+                    new_value: Some("<#2>".to_string()),
+                },
+                AuditChange {
+                    item_id: Some(1),
+                    field: "tracked",
+                    old_value: Some("yes".to_string()),
+                    new_value: None,
+                },
+                AuditChange {
+                    item_id: Some(3),
+                    field: "tracked",
+                    old_value: None,
+                    new_value: Some("yes".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_tells_values_of_a_field_apart() {
+        let mut before = Snapshot::default();
+        before.set_nth(Some(1), "group", "a".to_string(), "a".to_string());
+
+        let mut after = before.clone();
+        after.set_nth(Some(1), "group", "b".to_string(), "b".to_string());
+
+        assert_eq!(
+            diff(&before, &after),
+            vec![AuditChange {
+                item_id: Some(1),
+                field: "group",
+                old_value: None,
+                new_value: Some("b".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_truncates_long_values() {
+        let before = Snapshot::default();
+        let mut after = Snapshot::default();
+        after.set(None, "header template", "x".repeat(1000));
+
+        let changes = diff(&before, &after);
+
+        assert_eq!(
+            changes[0].new_value.as_ref().unwrap().chars().count(),
+            MAX_VALUE_LENGTH
+        );
+    }
+
+    #[test]
+    fn fingerprints_hide_secrets() {
+        let fingerprint = fingerprint("https://discord.com/api/webhooks/1/token");
+
+        assert_eq!(fingerprint.len(), 9);
+        assert!(!fingerprint.contains("token"));
+    }
+}
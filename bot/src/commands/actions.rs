@@ -2,11 +2,22 @@ use poise::serenity_prelude::{Guild, GuildChannel};
 
 pub mod add;
 pub mod add_multiple;
+pub mod audit;
+pub mod check_now;
+pub mod deployed;
+pub mod export;
+pub mod groups;
 pub mod list;
+pub mod mute;
 pub mod notes;
+pub mod pending;
 pub mod remove;
+pub mod remove_collection;
+pub mod remove_multiple;
 pub mod restart;
 pub mod summary;
+pub mod trash;
+pub mod watch;
 
 pub fn get_guild_channel(g: &Guild, channel_id: u64) -> Option<GuildChannel> {
     g.channels
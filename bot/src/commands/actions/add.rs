@@ -1,15 +1,27 @@
 use crate::{
-    commands::common::{get_channel, get_guild, get_guild_channel, ok_or_respond},
+    commands::{
+        checks::is_contributor,
+        common::{get_channel, get_guild, get_guild_channel, ok_or_respond},
+    },
     db,
+    i18n::tr,
     steam::get_item,
     Context, Error,
 };
 
 /// Add a item to the tracked items
-#[poise::command(slash_command, rename = "add")]
+#[poise::command(
+    slash_command,
+    rename = "add",
+    check = "is_contributor",
+    name_localized("de", "hinzufügen"),
+    description_localized("de", "Füge einen Eintrag zu den überwachten Einträgen hinzu")
+)]
 pub async fn item_add(
     ctx: Context<'_>,
-    #[description = "The id of the item to be tracked"] item_id: u64,
+    #[description = "The id of the item to be tracked"]
+    #[description_localized("de", "Die ID des zu überwachenden Eintrags")]
+    item_id: u64,
 ) -> Result<(), Error> {
     let guild = get_guild!(ctx);
 
@@ -26,23 +38,30 @@ pub async fn item_add(
         db::subscriptions::check_subscription(&ctx.data().pool, guild.id.0, item_info.id),
         "An error occurred while checking the item."
     ) {
-        ctx.say("Item already tracked.").await?;
+        ctx.say(tr!(ctx, "Item already tracked.")).await?;
 
         return Ok(());
     }
 
     ok_or_respond!(
         ctx,
-        db::subscriptions::add_subscription(&ctx.data().pool, guild.id.0, item_info.id),
+        db::subscriptions::add_subscription(
+            &ctx.data().pool,
+            guild.id.0,
+            item_info.id,
+            ctx.author().id.0,
+            None
+        ),
         "An error occurred while adding the item."
     );
 
     let g = get_guild_channel!(ctx, guild, item_channel);
 
     g.send_message(ctx, |d| {
-        d.content(format!(
-            "Added item {} to the tracked items:",
-            item_info.name
+        d.content(tr!(
+            ctx,
+            "Added item {name} to the tracked items:",
+            name = item_info.name
         ));
 
         if item_info.preview_url.is_some() {
@@ -64,7 +83,7 @@ pub async fn item_add(
     })
     .await?;
 
-    ctx.say("Success").await?;
+    ctx.say(tr!(ctx, "Success")).await?;
 
     Ok(())
 }
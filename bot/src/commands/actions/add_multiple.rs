@@ -2,17 +2,32 @@ use poise::serenity_prelude::{Guild, GuildChannel};
 use tracing::error;
 
 use crate::{
-    commands::common::{get_channel, get_guild, get_guild_channel, ok_or_respond},
+    commands::{
+        checks::is_contributor,
+        common::{get_channel, get_guild, get_guild_channel, ok_or_respond},
+    },
     db,
+    i18n::tr,
     steam::{self, get_item},
     Context, Error,
 };
 
 /// Add multiple items to the tracked items
-#[poise::command(slash_command, rename = "add_multiple")]
+#[poise::command(
+    slash_command,
+    rename = "add_multiple",
+    check = "is_contributor",
+    name_localized("de", "mehrere_hinzufügen"),
+    description_localized("de", "Füge mehrere Einträge zu den überwachten Einträgen hinzu")
+)]
 pub async fn item_batch_add(
     ctx: Context<'_>,
-    #[description = "The id of the item to be tracked. Separated by a comma(,)"] item_ids: String,
+    #[description = "The id of the item to be tracked. Separated by a comma(,)"]
+    #[description_localized(
+        "de",
+        "Die IDs der zu überwachenden Einträge, durch Komma (,) getrennt"
+    )]
+    item_ids: String,
 ) -> Result<(), Error> {
     let mut errors = vec![];
     let item_ids: Vec<_> = item_ids
@@ -25,10 +40,10 @@ pub async fn item_batch_add(
         .collect();
 
     if !errors.is_empty() {
-        ctx.say("An error occurred while parsing the item ids.")
+        ctx.say(tr!(ctx, "An error occurred while parsing the item ids."))
             .await?;
         for error in errors {
-            ctx.say(format!("Error: {}", error)).await?;
+            ctx.say(tr!(ctx, "Error: {error}", error = error)).await?;
         }
         return Ok(());
     }
@@ -39,16 +54,27 @@ pub async fn item_batch_add(
 
     let g = get_guild_channel!(ctx, guild, item_channel);
 
-    ctx.say("Success").await?;
+    ctx.say(tr!(ctx, "Success")).await?;
 
-    add_by_id(ctx, item_ids, guild, g).await
+    add_by_id(ctx, item_ids, None, guild, g).await
 }
 
 /// Add all members of a collection to the tracked items
-#[poise::command(slash_command, rename = "add_collection")]
+#[poise::command(
+    slash_command,
+    rename = "add_collection",
+    check = "is_contributor",
+    name_localized("de", "sammlung_hinzufügen"),
+    description_localized(
+        "de",
+        "Füge alle Einträge einer Sammlung zu den überwachten Einträgen hinzu"
+    )
+)]
 pub async fn collection_add(
     ctx: Context<'_>,
-    #[description = "The id of the collection to be added"] collection_id: u64,
+    #[description = "The id of the collection to be added"]
+    #[description_localized("de", "Die ID der hinzuzufügenden Sammlung")]
+    collection_id: u64,
 ) -> Result<(), Error> {
     let guild = get_guild!(ctx);
 
@@ -62,15 +88,21 @@ pub async fn collection_add(
         "An error occurred while fetching the collection."
     );
 
-    ctx.say(format!("Got collection. Adding {} items", collection.len()))
-        .await?;
+    ctx.say(tr!(
+        ctx,
+        "Got collection. Adding {count} items",
+        count = collection.len()
+    ))
+    .await?;
 
-    add_by_id(ctx, collection, guild, g).await
+    add_by_id(ctx, collection, Some(collection_id), guild, g).await
 }
 
+/// Adds the items one by one, `collection` is remembered as where they came from
 async fn add_by_id(
     ctx: Context<'_>,
     item_ids: Vec<u64>,
+    collection: Option<u64>,
     guild: Guild,
     g: GuildChannel,
 ) -> Result<(), Error> {
@@ -79,9 +111,10 @@ async fn add_by_id(
             Ok(item_info) => item_info,
             Err(_) => {
                 g.send_message(ctx, |d| {
-                    d.content(format!(
-                        "An error occurred while fetching the item {}.",
-                        item_id
+                    d.content(tr!(
+                        ctx,
+                        "An error occurred while fetching the item {id}.",
+                        id = item_id
                     ));
                     d
                 })
@@ -90,7 +123,13 @@ async fn add_by_id(
             }
         };
 
-        match db::subscriptions::add_subscription(&ctx.data().pool, guild.id.0, item_info.id) {
+        match db::subscriptions::add_subscription(
+            &ctx.data().pool,
+            guild.id.0,
+            item_info.id,
+            ctx.author().id.0,
+            collection,
+        ) {
             Ok(_) => (),
             Err(_) => {
                 error!(
@@ -98,9 +137,10 @@ async fn add_by_id(
                     item_id
                 );
                 g.send_message(ctx, |d| {
-                    d.content(format!(
-                        "An error occurred while subscribing to the item {}.",
-                        item_info.name
+                    d.content(tr!(
+                        ctx,
+                        "An error occurred while subscribing to the item {name}.",
+                        name = item_info.name
                     ));
                     d
                 })
@@ -110,9 +150,10 @@ async fn add_by_id(
         };
 
         g.send_message(ctx, |d| {
-            d.content(format!(
-                "Added item {} to the tracked items:",
-                item_info.name
+            d.content(tr!(
+                ctx,
+                "Added item {name} to the tracked items:",
+                name = item_info.name
             ));
 
             d.embed(|e| {
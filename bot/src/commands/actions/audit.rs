@@ -0,0 +1,111 @@
+use poise::serenity_prelude as serenity;
+
+use crate::{
+    commands::{
+        autocomplete::autocomplete_name,
+        checks::is_manager,
+        common::{get_by_name, get_guild, ok_or_respond},
+    },
+    db::{self, audit::AuditEntry},
+    i18n::tr,
+    printer, Context, Error,
+};
+
+/// How many changes are shown at once
+const AUDIT_LIMIT: u64 = 50;
+
+/// Browse who changed the tracked items and settings
+#[poise::command(
+    slash_command,
+    rename = "audit",
+    check = "is_manager",
+    ephemeral,
+    name_localized("de", "protokoll"),
+    description_localized(
+        "de",
+        "Zeige, wer die überwachten Einträge und Einstellungen geändert hat"
+    )
+)]
+pub async fn audit(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "Only show the changes of this item"]
+    #[description_localized("de", "Nur die Änderungen dieses Eintrags anzeigen")]
+    item: Option<String>,
+    #[description = "Only show the changes made by this user"]
+    #[description_localized("de", "Nur die Änderungen dieses Benutzers anzeigen")]
+    user: Option<serenity::User>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let item_id = match item {
+        Some(item) => Some(get_by_name!(ctx, item).id),
+        None => None,
+    };
+
+    let entries = ok_or_respond!(
+        ctx,
+        db::audit::get_entries(
+            &ctx.data().pool,
+            guild.id.0,
+            item_id,
+            user.map(|u| u.id.0),
+            AUDIT_LIMIT
+        ),
+        "An error occurred while fetching the audit log."
+    );
+
+    if entries.is_empty() {
+        ctx.say(tr!(ctx, "No changes have been recorded.")).await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = entries.iter().map(|e| render_entry(ctx, e)).collect();
+
+    for message in printer::split_message(&tr!(ctx, "Latest changes:"), &lines) {
+        ctx.send(|m| m.content(message).allowed_mentions(|am| am.empty_parse()))
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn render_entry(ctx: Context<'_>, entry: &AuditEntry) -> String {
+    let value = |v: &Option<String>| match v {
+        Some(v) => printer::truncate(v, 100),
+        None => "–".to_string(),
+    };
+
+    let change = match &entry.item {
+        Some((id, name)) => {
+            let item = match name {
+                Some(name) => format!("{} ({})", name, id),
+                None => id.to_string(),
+            };
+
+            match (entry.field.as_str(), entry.new_value.as_deref()) {
+                ("tracked", Some("yes")) => tr!(ctx, "added {item}", item = item),
+                ("tracked", _) => tr!(ctx, "removed {item}", item = item),
+                (field, _) => tr!(
+                    ctx,
+                    "{field} of {item}: {old} → {new}",
+                    field = tr!(ctx, field),
+                    item = item,
+                    old = value(&entry.old_value),
+                    new = value(&entry.new_value)
+                ),
+            }
+        }
+        None => format!(
+            "{}: {} → {}",
+            tr!(ctx, entry.field),
+            value(&entry.old_value),
+            value(&entry.new_value)
+        ),
+    };
+
+    format!(
+        "<t:{}:f> <@{}> /{}: {}",
+        entry.created_at, entry.user_id, entry.command, change
+    )
+}
@@ -0,0 +1,102 @@
+use std::time;
+
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+
+use crate::{
+    commands::{
+        checks::is_contributor,
+        common::{get_guild, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    printer, Context, Error,
+};
+
+/// How long a guild has to wait between two manual checks
+const COOLDOWN: time::Duration = time::Duration::from_secs(60 * 5);
+
+lazy_static! {
+    static ref LAST_CHECKS: DashMap<u64, time::Instant> = DashMap::new();
+}
+
+/// Check all items for updates right now, without touching the schedule
+#[poise::command(
+    slash_command,
+    rename = "check_now",
+    check = "is_contributor",
+    name_localized("de", "jetzt_prüfen"),
+    description_localized(
+        "de",
+        "Prüfe alle Einträge sofort auf Updates, ohne den Zeitplan zu ändern"
+    )
+)]
+pub async fn check_now(ctx: Context<'_>) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    if let Some(last) = LAST_CHECKS.get(&guild.id.0) {
+        let elapsed = last.elapsed();
+        if elapsed < COOLDOWN {
+            ctx.say(tr!(
+                ctx,
+                "Please wait {seconds} more seconds before checking again.",
+                seconds = (COOLDOWN - elapsed).as_secs()
+            ))
+            .await?;
+            return Ok(());
+        }
+    }
+
+    let pool = &ctx.data().pool;
+
+    let (channel, webhook, schedule) = ok_or_respond!(
+        ctx,
+        db::servers::get_update_channel(pool, guild.id.0).and_then(|c| Ok((
+            c,
+            db::servers::get_webhook(pool, guild.id.0)?,
+            db::servers::get_schedule(pool, guild.id.0)?
+        ))),
+        "An error occurred while fetching the update channel."
+    );
+
+    if (channel.is_none() && webhook.is_none()) || schedule.is_none() {
+        ctx.say(tr!(
+            ctx,
+            "Please set an update channel and a schedule first, or run /setup."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    LAST_CHECKS.insert(guild.id.0, time::Instant::now());
+
+    // Checking every item can take a while
+    ctx.defer().await?;
+
+    let summary = ok_or_respond!(
+        ctx,
+        printer::notify_on_updates(ctx.data().scheduler.clone(), guild.id.0, true).await,
+        "An error occurred while checking for updates."
+    );
+
+    let mut reply = tr!(
+        ctx,
+        "Checked {checked} items, {updated} updated, {failed} failed.",
+        checked = summary.checked,
+        updated = summary.updated,
+        failed = summary.failed
+    );
+
+    if summary.skipped > 0 {
+        reply.push(' ');
+        reply.push_str(&tr!(
+            ctx,
+            "{skipped} items were skipped, their last update was posted less than an hour ago.",
+            skipped = summary.skipped
+        ));
+    }
+
+    ctx.say(reply).await?;
+
+    Ok(())
+}
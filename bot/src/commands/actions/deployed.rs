@@ -0,0 +1,125 @@
+use crate::{
+    commands::{
+        autocomplete::autocomplete_name,
+        checks::is_contributor,
+        common::{get_by_name, get_guild, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    printer, Context, Error,
+};
+
+/// Record the latest version of an item, or of all items, as deployed on your server
+#[poise::command(
+    slash_command,
+    rename = "mark_deployed",
+    check = "is_contributor",
+    name_localized("de", "als_installiert_markieren"),
+    description_localized(
+        "de",
+        "Markiere die neueste Version eines oder aller Einträge als auf deinem Server installiert"
+    )
+)]
+pub async fn mark_deployed(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "The id or the name of the item"]
+    #[description_localized("de", "Die ID oder der Name des Eintrags")]
+    item: Option<String>,
+    #[description = "Mark everything as deployed as of now"]
+    #[description_localized("de", "Alles als jetzt installiert markieren")]
+    all: Option<bool>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let item_id = match (item, all.unwrap_or(false)) {
+        (_, true) => None,
+        (Some(item), false) => {
+            let item_info = get_by_name!(ctx, item);
+
+            if !ok_or_respond!(
+                ctx,
+                db::subscriptions::check_subscription(&ctx.data().pool, guild.id.0, item_info.id),
+                "An error occurred while checking the item."
+            ) {
+                ctx.say(tr!(ctx, "This item is not tracked.")).await?;
+                return Ok(());
+            }
+
+            Some(item_info.id)
+        }
+        (None, false) => {
+            ctx.say(tr!(
+                ctx,
+                "Please provide an item, or set all to mark everything as deployed."
+            ))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    ok_or_respond!(
+        ctx,
+        db::subscriptions::mark_deployed(&ctx.data().pool, guild.id.0, item_id),
+        "An error occurred while marking the item as deployed."
+    );
+
+    match item_id {
+        Some(_) => ctx.say(tr!(ctx, "Marked the item as deployed.")).await?,
+        None => ctx.say(tr!(ctx, "Marked all items as deployed.")).await?,
+    };
+
+    Ok(())
+}
+
+/// List all items your server is behind on
+#[poise::command(
+    slash_command,
+    rename = "pending_updates",
+    name_localized("de", "ausstehende_updates"),
+    description_localized("de", "Zeige alle Einträge, bei denen dein Server nicht aktuell ist")
+)]
+pub async fn pending_updates(ctx: Context<'_>) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let undeployed = ok_or_respond!(
+        ctx,
+        db::subscriptions::get_undeployed(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the items."
+    );
+
+    if undeployed.is_empty() {
+        ctx.say(tr!(ctx, "Your server is up to date.")).await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = undeployed
+        .iter()
+        .map(|(info, deployed)| {
+            let deployed = match deployed {
+                Some(d) => format!("<t:{}:R>", d),
+                None => tr!(ctx, "never marked"),
+            };
+            tr!(
+                ctx,
+                "{name}: updated <t:{updated}:R>, deployed {deployed} <https://steamcommunity.com/sharedfiles/filedetails/?id={id}>",
+                name = info.name,
+                updated = info.last_updated,
+                deployed = deployed,
+                id = info.id
+            )
+        })
+        .collect();
+
+    let header = tr!(
+        ctx,
+        "Your server is behind on {count} items:",
+        count = undeployed.len()
+    );
+
+    for message in printer::split_message(&header, &lines) {
+        ctx.say(message).await?;
+    }
+
+    Ok(())
+}
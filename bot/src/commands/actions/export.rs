@@ -0,0 +1,80 @@
+use std::borrow::Cow;
+
+use poise::serenity_prelude::AttachmentType;
+
+use crate::{
+    commands::{
+        autocomplete::autocomplete_group,
+        common::{get_group, get_guild, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    Context, Error,
+};
+
+/// Export the ids of the tracked items, ready to be used with /add_multiple
+#[poise::command(
+    slash_command,
+    rename = "export",
+    name_localized("de", "exportieren"),
+    description_localized("de", "Exportiere die IDs der überwachten Einträge für /add_multiple")
+)]
+pub async fn export(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_group"]
+    #[description = "Only export the items of this group"]
+    #[description_localized("de", "Nur die Einträge dieser Gruppe exportieren")]
+    group: Option<String>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let mut subscriptions = ok_or_respond!(
+        ctx,
+        db::subscriptions::get_all_subscriptions_of_guild(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the subscriptions."
+    );
+
+    if let Some(group) = &group {
+        let group_id = get_group!(ctx, guild.id.0, group);
+
+        let members = ok_or_respond!(
+            ctx,
+            db::groups::get_group_items(&ctx.data().pool, group_id),
+            "An error occurred while fetching the group."
+        );
+
+        subscriptions.retain(|(_, info, _)| members.contains(&info.id));
+    }
+
+    if subscriptions.is_empty() {
+        ctx.say(tr!(ctx, "There are no tracked items.")).await?;
+        return Ok(());
+    }
+
+    let ids = subscriptions
+        .iter()
+        .map(|(_, info, _)| info.id.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let filename = match &group {
+        Some(group) => format!("{}.txt", group),
+        None => "items.txt".to_string(),
+    };
+
+    ctx.send(|m| {
+        m.content(tr!(
+            ctx,
+            "Exported {count} items.",
+            count = subscriptions.len()
+        ));
+        m.attachment(AttachmentType::Bytes {
+            data: Cow::Owned(ids.into_bytes()),
+            filename,
+        });
+        m
+    })
+    .await?;
+
+    Ok(())
+}
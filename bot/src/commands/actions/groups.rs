@@ -0,0 +1,222 @@
+use crate::{
+    commands::{
+        autocomplete::{autocomplete_group, autocomplete_name},
+        checks::{is_contributor, is_manager},
+        common::{get_by_name, get_group, get_guild, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    printer, Context, Error,
+};
+
+/// Create a group to bundle items, e.g. the mods of a modpack
+#[poise::command(
+    slash_command,
+    rename = "create_group",
+    check = "is_manager",
+    name_localized("de", "gruppe_erstellen"),
+    description_localized(
+        "de",
+        "Erstelle eine Gruppe für Einträge, z.B. die Mods eines Modpacks"
+    )
+)]
+pub async fn create_group(
+    ctx: Context<'_>,
+    #[description = "The name of the group"]
+    #[description_localized("de", "Der Name der Gruppe")]
+    #[max_length = 100]
+    name: String,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let name = name.trim();
+
+    if ok_or_respond!(
+        ctx,
+        db::groups::get_group_id(&ctx.data().pool, guild.id.0, name),
+        "An error occurred while checking the group."
+    )
+    .is_some()
+    {
+        ctx.say(tr!(
+            ctx,
+            "A group called {name} already exists.",
+            name = name
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    ok_or_respond!(
+        ctx,
+        db::groups::create_group(&ctx.data().pool, guild.id.0, name),
+        "An error occurred while creating the group."
+    );
+
+    ctx.say(tr!(ctx, "Created group {name}.", name = name))
+        .await?;
+
+    Ok(())
+}
+
+/// Delete a group. The items in it stay tracked
+#[poise::command(
+    slash_command,
+    rename = "delete_group",
+    check = "is_manager",
+    name_localized("de", "gruppe_löschen"),
+    description_localized("de", "Lösche eine Gruppe. Die Einträge darin werden weiter überwacht")
+)]
+pub async fn delete_group(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_group"]
+    #[description = "The name of the group"]
+    #[description_localized("de", "Der Name der Gruppe")]
+    name: String,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let group_id = get_group!(ctx, guild.id.0, name);
+
+    ok_or_respond!(
+        ctx,
+        db::groups::delete_group(&ctx.data().pool, group_id),
+        "An error occurred while deleting the group."
+    );
+
+    ctx.say(tr!(ctx, "Deleted group {name}.", name = name))
+        .await?;
+
+    Ok(())
+}
+
+/// Add a tracked item to a group
+#[poise::command(
+    slash_command,
+    rename = "group_add",
+    check = "is_contributor",
+    name_localized("de", "gruppe_hinzufügen"),
+    description_localized("de", "Füge einen überwachten Eintrag zu einer Gruppe hinzu")
+)]
+pub async fn group_add(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_group"]
+    #[description = "The name of the group"]
+    #[description_localized("de", "Der Name der Gruppe")]
+    group: String,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "The id or the name of the item"]
+    #[description_localized("de", "Die ID oder der Name des Eintrags")]
+    item: String,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let group_id = get_group!(ctx, guild.id.0, group);
+    let item_info = get_by_name!(ctx, item);
+
+    if !ok_or_respond!(
+        ctx,
+        db::subscriptions::check_subscription(&ctx.data().pool, guild.id.0, item_info.id),
+        "An error occurred while checking the item."
+    ) {
+        ctx.say(tr!(ctx, "This item is not tracked.")).await?;
+        return Ok(());
+    }
+
+    ok_or_respond!(
+        ctx,
+        db::groups::add_member(&ctx.data().pool, group_id, guild.id.0, item_info.id),
+        "An error occurred while adding the item to the group."
+    );
+
+    ctx.say(tr!(
+        ctx,
+        "Added {name} to {group}.",
+        name = item_info.name,
+        group = group
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Remove an item from a group. The item stays tracked
+#[poise::command(
+    slash_command,
+    rename = "group_remove",
+    check = "is_contributor",
+    name_localized("de", "gruppe_entfernen"),
+    description_localized(
+        "de",
+        "Entferne einen Eintrag aus einer Gruppe. Er wird weiter überwacht"
+    )
+)]
+pub async fn group_remove(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_group"]
+    #[description = "The name of the group"]
+    #[description_localized("de", "Der Name der Gruppe")]
+    group: String,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "The id or the name of the item"]
+    #[description_localized("de", "Die ID oder der Name des Eintrags")]
+    item: String,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let group_id = get_group!(ctx, guild.id.0, group);
+    let item_info = get_by_name!(ctx, item);
+
+    ok_or_respond!(
+        ctx,
+        db::groups::remove_member(&ctx.data().pool, group_id, item_info.id),
+        "An error occurred while removing the item from the group."
+    );
+
+    ctx.say(tr!(
+        ctx,
+        "Removed {name} from {group}.",
+        name = item_info.name,
+        group = group
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// List all groups
+#[poise::command(
+    slash_command,
+    rename = "groups",
+    name_localized("de", "gruppen"),
+    description_localized("de", "Zeige alle Gruppen")
+)]
+pub async fn groups(ctx: Context<'_>) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let groups = ok_or_respond!(
+        ctx,
+        db::groups::get_groups(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the groups."
+    );
+
+    if groups.is_empty() {
+        ctx.say(tr!(
+            ctx,
+            "There are no groups. Create one with /create_group."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = groups
+        .iter()
+        .map(|(name, count)| tr!(ctx, "{name}: {count} items", name = name, count = count))
+        .collect();
+
+    for message in printer::split_message(&tr!(ctx, "Groups:"), &lines) {
+        ctx.say(message).await?;
+    }
+
+    Ok(())
+}
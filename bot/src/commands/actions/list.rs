@@ -1,69 +1,379 @@
-use itertools::Itertools;
+use std::time::Duration;
+
+use poise::serenity_prelude::{
+    ButtonStyle, CreateComponents, CreateEmbed, InteractionResponseType,
+};
 
 use crate::{
-    commands::common::{get_guild, ok_or_respond},
-    db, Context, Error,
+    commands::{
+        autocomplete::autocomplete_group,
+        common::{get_group, get_guild, ok_or_respond},
+    },
+    db::{self, ItemInfo},
+    i18n::tr,
+    Context, Error,
 };
 
+const PAGE_SIZE: usize = 10;
+
+/// How long the buttons of the list keep working
+const TIMEOUT: Duration = Duration::from_secs(60 * 10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortBy {
+    Name,
+    LastUpdated,
+    DateAdded,
+}
+
+impl SortBy {
+    const ALL: [SortBy; 3] = [SortBy::Name, SortBy::LastUpdated, SortBy::DateAdded];
+
+    fn id(&self) -> &'static str {
+        match self {
+            SortBy::Name => "name",
+            SortBy::LastUpdated => "updated",
+            SortBy::DateAdded => "added",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            SortBy::Name => "Sort by name",
+            SortBy::LastUpdated => "Sort by last updated",
+            SortBy::DateAdded => "Sort by date added",
+        }
+    }
+}
+
+struct Entry {
+    info: ItemInfo,
+    added_at: Option<u64>,
+    line: String,
+}
+
 /// List all the currently subscribed items
-#[poise::command(slash_command, rename = "list")]
-pub async fn list_items(ctx: Context<'_>) -> Result<(), Error> {
+#[poise::command(
+    slash_command,
+    rename = "list",
+    ephemeral,
+    name_localized("de", "liste"),
+    description_localized("de", "Zeige alle überwachten Einträge")
+)]
+pub async fn list_items(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_group"]
+    #[description = "Only list the items of this group"]
+    #[description_localized("de", "Nur die Einträge dieser Gruppe zeigen")]
+    group: Option<String>,
+    #[description = "Only list muted or only unmuted items"]
+    #[description_localized(
+        "de",
+        "Nur stummgeschaltete oder nur nicht stummgeschaltete Einträge zeigen"
+    )]
+    muted: Option<bool>,
+    #[description = "Only list items with or without a note"]
+    #[description_localized("de", "Nur Einträge mit oder ohne Notiz zeigen")]
+    has_note: Option<bool>,
+) -> Result<(), Error> {
     let guild = get_guild!(ctx);
 
-    let subscriptions = ok_or_respond!(
+    let mut subscriptions = ok_or_respond!(
         ctx,
         db::subscriptions::get_all_subscriptions_of_guild(&ctx.data().pool, guild.id.0),
         "An error occurred while fetching the subscriptions."
     );
 
+    if let Some(group) = group {
+        let group_id = get_group!(ctx, guild.id.0, group);
+
+        let members = ok_or_respond!(
+            ctx,
+            db::groups::get_group_items(&ctx.data().pool, group_id),
+            "An error occurred while fetching the group."
+        );
+
+        subscriptions.retain(|(_, info, _)| members.contains(&info.id));
+    }
+
+    let groups = ok_or_respond!(
+        ctx,
+        db::groups::get_item_groups(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the groups."
+    );
+
+    let muted_items = ok_or_respond!(
+        ctx,
+        db::subscriptions::get_muted_items(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the muted items."
+    );
+
+    let schedule = ok_or_respond!(
+        ctx,
+        db::servers::get_schedule(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the schedule."
+    );
+
+    let intervals = ok_or_respond!(
+        ctx,
+        db::subscriptions::get_check_intervals(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the check intervals."
+    );
+
+    let provenance = ok_or_respond!(
+        ctx,
+        db::subscriptions::get_provenance(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the subscriptions."
+    );
+
+    if let Some(muted) = muted {
+        subscriptions.retain(|(_, info, _)| muted_items.contains_key(&info.id) == muted);
+    }
+
+    if let Some(has_note) = has_note {
+        subscriptions.retain(|(_, _, note)| note.is_some() == has_note);
+    }
+
     if subscriptions.is_empty() {
-        ctx.say("There are no tracked items.").await?;
+        ctx.say(tr!(ctx, "There are no tracked items.")).await?;
         return Ok(());
     }
 
-    if subscriptions.len() > 10 {
-        let items: Vec<String> = subscriptions
-            .iter()
-            .chunks(10)
-            .into_iter()
-            .map(|chunk| {
-                chunk
-                    .map(|(_, info, _)| {
-                        format!(
-                            "\n{}: <https://steamcommunity.com/sharedfiles/filedetails/?id={}>",
-                            info.name, info.id
-                        )
-                    })
-                    .collect::<Vec<String>>()
-                    .join(", ")
-            })
-            .collect();
-        let parts = items.len();
-
-        for (i, part) in items.iter().enumerate() {
-            ctx.say(format!(
-                "Currently tracked items (part {} of {}): {}",
-                i + 1,
-                parts,
-                part
-            ))
-            .await?;
+    let mut entries: Vec<Entry> = subscriptions
+        .into_iter()
+        .map(|(_, info, note)| {
+            let mut line = format!(
+                "[{}](https://steamcommunity.com/sharedfiles/filedetails/?id={}), {}",
+                info.name,
+                info.id,
+                tr!(ctx, "updated <t:{time}:R>", time = info.last_updated)
+            );
+
+            match intervals
+                .get(&info.id)
+                .copied()
+                .or(schedule.map(|h| h * 60))
+            {
+                Some(minutes) => line.push_str(&tr!(
+                    ctx,
+                    ", every {interval}",
+                    interval = format_interval(minutes)
+                )),
+                None => line.push_str(&tr!(ctx, ", not checked")),
+            }
+
+            if let Some(g) = groups.get(&info.id) {
+                line.push_str(&format!(" [{}]", g.join(", ")));
+            }
+
+            match muted_items.get(&info.id) {
+                Some(Some(until)) => {
+                    line.push_str(&tr!(ctx, " :mute: until <t:{until}:R>", until = until))
+                }
+                Some(None) => line.push_str(" :mute:"),
+                None => {}
+            }
+
+            if note.is_some() {
+                line.push_str(" :memo:");
+            }
+
+            let provenance = provenance.get(&info.id).cloned().unwrap_or_default();
+
+            match (provenance.added_by, provenance.added_at) {
+                (Some(user), Some(time)) => line.push_str(&tr!(
+                    ctx,
+                    ", added by <@{user}> <t:{time}:R>",
+                    user = user,
+                    time = time
+                )),
+                (Some(user), None) => line.push_str(&tr!(ctx, ", added by <@{user}>", user = user)),
+                (None, Some(time)) => line.push_str(&tr!(ctx, ", added <t:{time}:R>", time = time)),
+                (None, None) => {}
+            }
+
+            if let Some(collection) = provenance.collection {
+                line.push_str(&tr!(
+                    ctx,
+                    " from collection {collection}",
+                    collection = collection
+                ));
+            }
+
+            Entry {
+                added_at: provenance.added_at,
+                info,
+                line,
+            }
+        })
+        .collect();
+
+    let mut sort = SortBy::Name;
+    let mut page = 0;
+    sort_entries(&mut entries, sort);
+
+    let pages = entries.len().div_ceil(PAGE_SIZE);
+
+    let reply = ctx
+        .send(|b| {
+            b.embed(|e| {
+                *e = render_page(ctx, &entries, page, pages);
+                e
+            });
+            b.components(|c| {
+                *c = list_components(ctx, page, pages, sort);
+                c
+            });
+            b
+        })
+        .await?;
+
+    let message = reply.message().await?;
+
+    while let Some(mc) = message
+        .await_component_interaction(ctx)
+        .author_id(ctx.author().id)
+        .timeout(TIMEOUT)
+        .await
+    {
+        match mc.data.custom_id.as_str() {
+            "list:prev" => page = page.saturating_sub(1),
+            "list:next" => page = (page + 1).min(pages.saturating_sub(1)),
+            "list:sort" => {
+                let selected = mc.data.values.first().map(String::as_str);
+                if let Some(s) = SortBy::ALL.into_iter().find(|s| Some(s.id()) == selected) {
+                    sort = s;
+                    page = 0;
+                    sort_entries(&mut entries, sort);
+                }
+            }
+            _ => {
+                // Every interaction has to be answered, or Discord shows it as failed
+                mc.create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::DeferredUpdateMessage)
+                })
+                .await?;
+                continue;
+            }
         }
-    } else {
-        let items = subscriptions
-            .iter()
-            .map(|(_, info, _)| {
-                format!(
-                    "\n{}: <https://steamcommunity.com/sharedfiles/filedetails/?id={}>",
-                    info.name, info.id
-                )
-            })
-            .collect::<Vec<String>>()
-            .join(", ");
-
-        ctx.say(format!("Currently tracked items: {}", items))
-            .await?;
+
+        mc.create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::UpdateMessage);
+            r.interaction_response_data(|d| {
+                d.add_embed(render_page(ctx, &entries, page, pages));
+                d.set_components(list_components(ctx, page, pages, sort));
+                d
+            });
+            r
+        })
+        .await?;
     }
 
+    // The buttons stop working once we stop listening
+    reply.edit(ctx, |b| b.components(|c| c)).await?;
+
     Ok(())
 }
+
+fn sort_entries(entries: &mut [Entry], sort: SortBy) {
+    match sort {
+        SortBy::Name => entries.sort_by_key(|e| e.info.name.to_lowercase()),
+        SortBy::LastUpdated => entries.sort_by_key(|e| std::cmp::Reverse(e.info.last_updated)),
+        // Items without a known date are the oldest
+        SortBy::DateAdded => entries.sort_by_key(|e| std::cmp::Reverse(e.added_at)),
+    }
+}
+
+fn render_page(ctx: Context<'_>, entries: &[Entry], page: usize, pages: usize) -> CreateEmbed {
+    let mut e = CreateEmbed::default();
+
+    e.title(tr!(ctx, "Currently tracked items"));
+    e.description(
+        entries
+            .iter()
+            .skip(page * PAGE_SIZE)
+            .take(PAGE_SIZE)
+            .map(|entry| entry.line.as_str())
+            .collect::<Vec<&str>>()
+            .join("\n"),
+    );
+    e.footer(|f| {
+        f.text(tr!(
+            ctx,
+            "Page {page} of {pages} - {count} items",
+            page = page + 1,
+            pages = pages,
+            count = entries.len()
+        ));
+        f
+    });
+
+    e
+}
+
+fn list_components(ctx: Context<'_>, page: usize, pages: usize, sort: SortBy) -> CreateComponents {
+    let mut c = CreateComponents::default();
+
+    c.create_action_row(|r| {
+        r.create_button(|b| {
+            b.style(ButtonStyle::Secondary);
+            b.label(tr!(ctx, "Prev"));
+            b.custom_id("list:prev");
+            b.disabled(page == 0);
+            b
+        });
+        r.create_button(|b| {
+            b.style(ButtonStyle::Secondary);
+            b.label(tr!(ctx, "Next"));
+            b.custom_id("list:next");
+            b.disabled(page + 1 >= pages);
+            b
+        });
+        r
+    });
+
+    c.create_action_row(|r| {
+        r.create_select_menu(|m| {
+            m.custom_id("list:sort");
+            m.options(|o| {
+                for s in SortBy::ALL {
+                    o.create_option(|opt| {
+                        opt.label(tr!(ctx, s.label()));
+                        opt.value(s.id());
+                        opt.default_selection(s == sort);
+                        opt
+                    });
+                }
+                o
+            });
+            m
+        });
+        r
+    });
+
+    c
+}
+
+fn format_interval(minutes: u64) -> String {
+    if minutes.is_multiple_of(60 * 24) {
+        format!("{}d", minutes / (60 * 24))
+    } else if minutes.is_multiple_of(60) {
+        format!("{}h", minutes / 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals_use_the_largest_whole_unit() {
+        assert_eq!(format_interval(45), "45m");
+        assert_eq!(format_interval(120), "2h");
+        assert_eq!(format_interval(90), "90m");
+        assert_eq!(format_interval(60 * 24 * 2), "2d");
+        assert_eq!(format_interval(60 * 36), "36h");
+    }
+}
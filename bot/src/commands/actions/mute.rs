@@ -0,0 +1,144 @@
+use std::time;
+
+use crate::{
+    commands::{
+        autocomplete::autocomplete_name,
+        checks::is_contributor,
+        common::{get_by_name, get_guild, ok_or_respond, parse_date, parse_duration},
+    },
+    db,
+    i18n::tr,
+    Context, Error,
+};
+
+/// Keep tracking an item but stop posting its updates
+#[poise::command(
+    slash_command,
+    rename = "mute",
+    check = "is_contributor",
+    name_localized("de", "stummschalten"),
+    description_localized(
+        "de",
+        "Überwache einen Eintrag weiter, aber poste seine Updates nicht mehr"
+    )
+)]
+pub async fn mute(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "The id or the name of the item"]
+    #[description_localized("de", "Die ID oder der Name des Eintrags")]
+    item: String,
+    #[description = "How long to mute the item, e.g. 3d or 2 weeks, or a date. Leave empty to mute it until /unmute"]
+    #[description_localized(
+        "de",
+        "Wie lange, z.B. 3d, 2 weeks oder ein Datum. Leer lassen bis zu /unmute"
+    )]
+    duration: Option<String>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let now = time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)?
+        .as_secs();
+
+    let until = match duration {
+        Some(duration) => match parse_duration(&duration) {
+            Some(secs) => Some(now + secs),
+            None => {
+                let tz = ok_or_respond!(
+                    ctx,
+                    db::servers::get_timezone(&ctx.data().pool, guild.id.0),
+                    "An error occurred while fetching the timezone."
+                );
+                match parse_date(&duration, &tz) {
+                    Some(until) if until > now => Some(until),
+                    Some(_) => {
+                        ctx.say(tr!(ctx, "The date has to be in the future."))
+                            .await?;
+                        return Ok(());
+                    }
+                    None => {
+                        ctx.say(tr!(
+                            ctx,
+                            "Invalid duration. Use something like 3d, 12h, 2 weeks or a date. Dates are in {timezone}.",
+                            timezone = tz.name()
+                        ))
+                        .await?;
+                        return Ok(());
+                    }
+                }
+            }
+        },
+        None => None,
+    };
+
+    let item_info = get_by_name!(ctx, item);
+
+    if !ok_or_respond!(
+        ctx,
+        db::subscriptions::check_subscription(&ctx.data().pool, guild.id.0, item_info.id),
+        "An error occurred while checking the item."
+    ) {
+        ctx.say(tr!(ctx, "This item is not tracked.")).await?;
+        return Ok(());
+    }
+
+    ok_or_respond!(
+        ctx,
+        db::subscriptions::mute_item(&ctx.data().pool, guild.id.0, item_info.id, until),
+        "An error occurred while muting the item."
+    );
+
+    match until {
+        Some(until) => {
+            ctx.say(tr!(
+                ctx,
+                "Muted {name} until <t:{until}:f>.",
+                name = item_info.name,
+                until = until
+            ))
+            .await?
+        }
+        None => {
+            ctx.say(tr!(
+                ctx,
+                "Muted {name} until you /unmute it.",
+                name = item_info.name
+            ))
+            .await?
+        }
+    };
+
+    Ok(())
+}
+
+/// Post the updates of a muted item again
+#[poise::command(
+    slash_command,
+    rename = "unmute",
+    check = "is_contributor",
+    name_localized("de", "stummschaltung_aufheben"),
+    description_localized("de", "Poste die Updates eines stummgeschalteten Eintrags wieder")
+)]
+pub async fn unmute(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "The id or the name of the item"]
+    #[description_localized("de", "Die ID oder der Name des Eintrags")]
+    item: String,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let item_info = get_by_name!(ctx, item);
+
+    ok_or_respond!(
+        ctx,
+        db::subscriptions::unmute_item(&ctx.data().pool, guild.id.0, item_info.id),
+        "An error occurred while unmuting the item."
+    );
+
+    ctx.say(tr!(ctx, "Unmuted {name}.", name = item_info.name))
+        .await?;
+
+    Ok(())
+}
@@ -4,59 +4,214 @@ type ApplicationContext<'a> = poise::ApplicationContext<'a, AppState, Error>;
 use crate::{
     commands::{
         autocomplete::autocomplete_name,
+        checks::is_contributor,
         common::{get_by_name, get_guild, ok_or_respond},
     },
-    db, AppState, Error,
+    db::{self, notes::NOTES_SHOWN},
+    i18n::tr,
+    AppState, Context, Error,
 };
 
 #[derive(Debug, Modal)]
-#[name = "Edit Note"]
+#[name = "Add Note"]
 struct NoteModal {
     #[name = "Note"]
     #[max_length = 500]
     #[paragraph]
-    note: Option<String>,
+    note: String,
 }
 
-impl NoteModal {
-    fn new(note: Option<String>) -> Self {
-        Self { note }
+/// Add a note to an item, the latest notes are shown with its updates
+#[poise::command(
+    slash_command,
+    rename = "add_note",
+    check = "is_contributor",
+    name_localized("de", "notiz_hinzufügen"),
+    description_localized(
+        "de",
+        "Füge einem Eintrag eine Notiz hinzu, die neuesten Notizen werden bei Updates angezeigt"
+    )
+)]
+pub async fn add_note(
+    ctx: ApplicationContext<'_>,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "The id or the name of the item"]
+    #[description_localized("de", "Die ID oder der Name des Eintrags")]
+    item: String,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let item_info = get_by_name!(ctx, item);
+
+    if !ok_or_respond!(
+        ctx,
+        db::subscriptions::check_subscription(&ctx.data().pool, guild.id.0, item_info.id),
+        "An error occurred while fetching the subscription."
+    ) {
+        ctx.say(tr!(ctx, "This item is not tracked by this server."))
+            .await?;
+        return Ok(());
     }
+
+    let response = match NoteModal::execute(ctx).await? {
+        Some(r) => r,
+        None => return Ok(()),
+    };
+
+    let note = response.note.trim();
+
+    if note.is_empty() {
+        ctx.say(tr!(ctx, "The note is empty.")).await?;
+        return Ok(());
+    }
+
+    ok_or_respond!(
+        ctx,
+        db::notes::add_note(
+            &ctx.data().pool,
+            guild.id.0,
+            item_info.id,
+            ctx.author().id.0,
+            note
+        ),
+        "An error occurred while updating the note."
+    );
+
+    ctx.say(tr!(ctx, "Success")).await?;
+
+    Ok(())
 }
 
-#[poise::command(slash_command, rename = "edit_note")]
-pub async fn edit_note(
-    ctx: ApplicationContext<'_>,
+/// Remove a note from an item, it stays in the history
+#[poise::command(
+    slash_command,
+    rename = "remove_note",
+    check = "is_contributor",
+    name_localized("de", "notiz_entfernen"),
+    description_localized(
+        "de",
+        "Entferne eine Notiz von einem Eintrag, sie bleibt im Verlauf erhalten"
+    )
+)]
+pub async fn remove_note(
+    ctx: Context<'_>,
     #[autocomplete = "autocomplete_name"]
     #[description = "The id or the name of the item"]
+    #[description_localized("de", "Die ID oder der Name des Eintrags")]
     item: String,
+    #[description = "The number of the note, as shown by /notes"]
+    #[description_localized("de", "Die Nummer der Notiz, wie von /notes angezeigt")]
+    note: u64,
 ) -> Result<(), Error> {
     let guild = get_guild!(ctx);
 
     let item_info = get_by_name!(ctx, item);
 
-    let note = ok_or_respond!(
+    let removed = ok_or_respond!(
         ctx,
-        db::subscriptions::get_note(&ctx.data().pool, guild.id.0, item_info.id),
-        "An error occurred while fetching the subscription."
+        db::notes::remove_note(
+            &ctx.data().pool,
+            guild.id.0,
+            item_info.id,
+            note,
+            ctx.author().id.0
+        ),
+        "An error occurred while updating the note."
     );
 
-    let response = NoteModal::execute_with_defaults(ctx, NoteModal::new(note)).await?;
+    if removed {
+        ctx.say(tr!(ctx, "Note removed.")).await?;
+    } else {
+        ctx.say(tr!(
+            ctx,
+            "{name} has no note #{note}. See /notes for its notes.",
+            name = item_info.name,
+            note = note
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Show every note ever written on an item
+#[poise::command(
+    slash_command,
+    rename = "notes",
+    ephemeral,
+    name_localized("de", "notizen"),
+    description_localized("de", "Zeige alle Notizen, die je zu einem Eintrag geschrieben wurden")
+)]
+pub async fn notes(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "The id or the name of the item"]
+    #[description_localized("de", "Die ID oder der Name des Eintrags")]
+    item: String,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let item_info = get_by_name!(ctx, item);
+
+    let history = ok_or_respond!(
+        ctx,
+        db::notes::get_history(&ctx.data().pool, guild.id.0, item_info.id),
+        "An error occurred while fetching the notes."
+    );
 
-    if let Some(note) = response {
-        ok_or_respond!(
+    if history.is_empty() {
+        ctx.say(tr!(
             ctx,
-            db::subscriptions::update_subscription_note(
-                &ctx.data().pool,
-                guild.id.0,
-                item_info.id,
-                note.note
-            ),
-            "An error occurred while updating the note."
-        );
+            "{name} has no notes. Add one with /add_note.",
+            name = item_info.name
+        ))
+        .await?;
+        return Ok(());
     }
 
-    ctx.say("Success").await?;
+    let mut shown = 0;
+    let lines: Vec<String> = history
+        .iter()
+        .map(|note| {
+            let mut line = match note.author_id {
+                Some(author) => format!("`#{}` <t:{}:f> <@{}>: ", note.id, note.created_at, author),
+                None => format!("`#{}` <t:{}:f>: ", note.id, note.created_at),
+            };
+
+            match note.removed {
+                Some((by, at)) => {
+                    line.push_str(&format!("~~{}~~ ", note.content));
+                    line.push_str(&tr!(
+                        ctx,
+                        "(removed by <@{user}> <t:{time}:R>)",
+                        user = by,
+                        time = at
+                    ));
+                }
+                None => {
+                    line.push_str(&note.content);
+                    // Only the latest notes are posted with updates
+                    shown += 1;
+                    if shown <= NOTES_SHOWN {
+                        line.push_str(" :pushpin:");
+                    }
+                }
+            }
+
+            line
+        })
+        .collect();
+
+    let header = tr!(ctx, "Notes of {name}:", name = item_info.name);
+
+    // Notes can be long, so only a few fit into one message
+    for part in lines.chunks(3) {
+        ctx.send(|m| {
+            m.content(format!("{}\n{}", header, part.join("\n")))
+                .allowed_mentions(|am| am.empty_parse())
+        })
+        .await?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,197 @@
+use std::time;
+
+use poise::serenity_prelude::{
+    self as serenity, ActionRowComponent, ButtonStyle, CreateComponents, InteractionResponseType,
+    MessageComponentInteraction,
+};
+
+use crate::{
+    commands::{
+        checks::member_has_level,
+        common::{get_guild, ok_or_respond},
+    },
+    db::{self, PermissionLevel},
+    i18n::{self, tr, translate},
+    printer, template, AppState, Context, Error,
+};
+
+/// How long the snooze button postpones an update
+const SNOOZE_DURATION: u64 = 60 * 60 * 24;
+
+/// List all updates nobody has handled yet
+#[poise::command(
+    slash_command,
+    rename = "pending",
+    name_localized("de", "offen"),
+    description_localized("de", "Zeige alle Updates, die noch niemand erledigt hat")
+)]
+pub async fn pending(ctx: Context<'_>) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let pending = ok_or_respond!(
+        ctx,
+        db::events::get_pending(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the pending updates."
+    );
+
+    if pending.is_empty() {
+        ctx.say(tr!(ctx, "All updates have been handled.")).await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = pending
+        .iter()
+        .map(|(name, id, version, posted_at)| {
+            tr!(
+                ctx,
+                "{name}: updated <t:{updated}:R>, posted <t:{posted}:R> <https://steamcommunity.com/sharedfiles/filedetails/?id={id}>",
+                name = name,
+                updated = version,
+                posted = posted_at,
+                id = id
+            )
+        })
+        .collect();
+
+    for message in printer::split_message(&tr!(ctx, "Pending updates:"), &lines) {
+        ctx.say(message).await?;
+    }
+
+    Ok(())
+}
+
+/// Handles the buttons added by the printer to update messages.
+/// The custom id has the form `ack:<action>:<item id>:<version>`.
+pub async fn handle_acknowledge(
+    ctx: &serenity::Context,
+    state: &AppState,
+    mc: &MessageComponentInteraction,
+) -> Result<(), Error> {
+    let parts: Vec<&str> = mc.data.custom_id.split(':').collect();
+
+    let (action, item_id, version): (&str, u64, u64) = match parts.as_slice() {
+        ["ack", action, item_id, version] => (action, item_id.parse()?, version.parse()?),
+        _ => return Err(format!("Invalid acknowledge id: {}", mc.data.custom_id).into()),
+    };
+
+    let guild_id = mc.guild_id.ok_or("Acknowledge outside of a guild")?.0;
+    let user = &mc.user;
+    let language = i18n::guild_language(&state.pool, Some(guild_id));
+
+    // Handling an update is up to the same members that may mark items as deployed
+    let member = mc.member.as_ref().ok_or("Acknowledge without a member")?;
+    if !member_has_level(&state.pool, guild_id, member, PermissionLevel::Contributor)? {
+        mc.create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource);
+            r.interaction_response_data(|d| {
+                d.content(translate(
+                    language,
+                    "You don't have permission to handle updates.",
+                ));
+                d.ephemeral(true);
+                d
+            });
+            r
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let name = db::items::get_item(&state.pool, item_id)?
+        .map(|i| i.name)
+        .unwrap_or_else(|| item_id.to_string());
+
+    let line = match action {
+        "handled" => {
+            db::events::mark_handled(&state.pool, guild_id, item_id, version, user.id.0)?;
+            template::render(
+                translate(
+                    language,
+                    ":white_check_mark: {name} was handled by <@{user}>",
+                ),
+                &[("name", name.clone()), ("user", user.id.0.to_string())],
+            )
+        }
+        "snooze" => {
+            let until = time::SystemTime::now()
+                .duration_since(time::UNIX_EPOCH)?
+                .as_secs()
+                + SNOOZE_DURATION;
+
+            db::events::snooze(&state.pool, guild_id, item_id, version, user.id.0, until)?;
+            template::render(
+                translate(
+                    language,
+                    ":zzz: {name} was snoozed by <@{user}> until <t:{until}:f>",
+                ),
+                &[
+                    ("name", name.clone()),
+                    ("user", user.id.0.to_string()),
+                    ("until", until.to_string()),
+                ],
+            )
+        }
+        _ => return Err(format!("Unknown acknowledge action: {}", action).into()),
+    };
+
+    let suffix = format!(":{}:{}", item_id, version);
+    let mut components = CreateComponents::default();
+
+    for row in &mc.message.components {
+        components.create_action_row(|r| {
+            for component in &row.components {
+                let button = match component {
+                    ActionRowComponent::Button(b) => b,
+                    _ => continue,
+                };
+
+                let custom_id = button.custom_id.clone().unwrap_or_default();
+                let is_this_item = custom_id.ends_with(&suffix);
+
+                // Handling an update closes its whole row, snoozing only the snooze button
+                if is_this_item && action == "handled" && custom_id.starts_with("ack:snooze") {
+                    continue;
+                }
+
+                r.create_button(|b| {
+                    b.custom_id(&custom_id);
+                    if is_this_item && custom_id.starts_with(&format!("ack:{}", action)) {
+                        let label = match action {
+                            "handled" => template::render(
+                                translate(language, "{name} handled by {user}"),
+                                &[("name", name.clone()), ("user", user.name.clone())],
+                            ),
+                            _ => template::render(
+                                translate(language, "Snoozed by {user}"),
+                                &[("user", user.name.clone())],
+                            ),
+                        };
+                        b.label(printer::truncate(&label, 80));
+                        b.style(ButtonStyle::Secondary);
+                        b.disabled(true);
+                    } else {
+                        b.label(button.label.clone().unwrap_or_default());
+                        b.style(button.style);
+                        b.disabled(button.disabled);
+                    }
+                    b
+                });
+            }
+            r
+        });
+    }
+
+    mc.create_interaction_response(ctx, |r| {
+        r.kind(InteractionResponseType::UpdateMessage);
+        r.interaction_response_data(|d| {
+            d.content(format!("{}\n{}", mc.message.content, line));
+            d.allowed_mentions(|am| am.empty_parse());
+            d.set_components(components);
+            d
+        });
+        r
+    })
+    .await?;
+
+    Ok(())
+}
@@ -1,18 +1,29 @@
 use crate::commands::autocomplete::autocomplete_name;
-use poise::serenity_prelude::ButtonStyle;
+use poise::{serenity_prelude::ButtonStyle, ReplyHandle};
 
 use crate::{
-    commands::common::{get_by_name, get_channel, get_guild, get_guild_channel, ok_or_respond},
+    commands::{
+        checks::is_manager,
+        common::{get_by_name, get_channel, get_guild, get_guild_channel, ok_or_respond},
+    },
     db::{self},
+    i18n::tr,
     Context, Error,
 };
 
 /// Remove an item from the tracked items
-#[poise::command(slash_command, rename = "remove")]
+#[poise::command(
+    slash_command,
+    rename = "remove",
+    check = "is_manager",
+    name_localized("de", "entfernen"),
+    description_localized("de", "Entferne einen Eintrag aus den überwachten Einträgen")
+)]
 pub async fn item_remove(
     ctx: Context<'_>,
     #[autocomplete = "autocomplete_name"]
     #[description = "The id of name of item to be removed"]
+    #[description_localized("de", "Die ID oder der Name des zu entfernenden Eintrags")]
     item: String,
 ) -> Result<(), Error> {
     let guild = get_guild!(ctx);
@@ -30,9 +41,10 @@ pub async fn item_remove(
     let g = get_guild_channel!(ctx, guild, item_channel);
 
     g.send_message(ctx, |d| {
-        d.content(format!(
-            "Removed item {} from the tracked items:",
-            item_info.name
+        d.content(tr!(
+            ctx,
+            "Removed item {name} from the tracked items:",
+            name = item_info.name
         ));
 
         d.embed(|e| {
@@ -52,32 +64,75 @@ pub async fn item_remove(
     })
     .await?;
 
-    ctx.say("Success").await?;
+    ctx.say(tr!(ctx, "Success. Use /undo_remove to restore it."))
+        .await?;
     Ok(())
 }
 
 /// Remove all items from the tracked items
-#[poise::command(slash_command, rename = "remove_all")]
+#[poise::command(
+    slash_command,
+    rename = "remove_all",
+    check = "is_manager",
+    name_localized("de", "alle_entfernen"),
+    description_localized("de", "Entferne alle überwachten Einträge")
+)]
 pub async fn remove_all(ctx: Context<'_>) -> Result<(), Error> {
     let guild = get_guild!(ctx);
 
     let item_channel = get_channel!(ctx, guild.id.0);
 
+    let reply = match confirm(ctx, &tr!(ctx, "Are you sure you want to remove all items?")).await? {
+        Some(reply) => reply,
+        None => return Ok(()),
+    };
+
+    ok_or_respond!(
+        ctx,
+        db::subscriptions::remove_all_subscriptions(&ctx.data().pool, guild.id.0),
+        "An error occurred while removing all items."
+    );
+
+    reply
+        .edit(ctx, |b| {
+            b.components(|b| b)
+                .content(tr!(ctx, "Done! Use /undo_remove to restore them."))
+        })
+        .await?;
+
+    let g = get_guild_channel!(ctx, guild, item_channel);
+
+    g.send_message(ctx, |d| {
+        d.content(tr!(ctx, "Removed all items."));
+
+        d
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Asks the author to confirm with a yes and a no button.
+/// Returns the message to report the result in if they confirmed.
+pub async fn confirm<'a>(
+    ctx: Context<'a>,
+    question: &str,
+) -> Result<Option<ReplyHandle<'a>>, Error> {
     let reply = ctx
         .send(|b| {
-            b.content("Are you sure you want to remove all items?");
+            b.content(question);
             b.components(|c| {
                 c.create_action_row(|r| {
                     r.create_button(|b| {
                         b.style(ButtonStyle::Primary);
-                        b.label("Yes");
+                        b.label(tr!(ctx, "Yes"));
                         b.custom_id("yes");
 
                         b
                     });
                     r.create_button(|b| {
                         b.style(ButtonStyle::Danger);
-                        b.label("No");
+                        b.label(tr!(ctx, "No"));
                         b.custom_id("no");
 
                         b
@@ -101,44 +156,29 @@ pub async fn remove_all(ctx: Context<'_>) -> Result<(), Error> {
 
     reply
         .edit(ctx, |b| {
-            b.components(|b| b).content("Processing... Please wait.")
+            b.components(|b| b)
+                .content(tr!(ctx, "Processing... Please wait."))
         })
         .await?;
     // remove buttons after button press and edit message
     let pressed_button_id = match &interaction {
         Some(m) => &m.data.custom_id,
         None => {
-            ctx.say(":warning: You didn't interact in time - please run the command again.")
-                .await?;
-            return Ok(());
+            ctx.say(tr!(
+                ctx,
+                ":warning: You didn't interact in time - please run the command again."
+            ))
+            .await?;
+            return Ok(None);
         }
     };
 
     if pressed_button_id == "no" {
         reply
-            .edit(ctx, |b| b.components(|b| b).content("Cancelled."))
+            .edit(ctx, |b| b.components(|b| b).content(tr!(ctx, "Cancelled.")))
             .await?;
-        return Ok(());
+        return Ok(None);
     }
 
-    ok_or_respond!(
-        ctx,
-        db::subscriptions::remove_all_subscriptions(&ctx.data().pool, guild.id.0),
-        "An error occurred while removing all items."
-    );
-
-    reply
-        .edit(ctx, |b| b.components(|b| b).content("Done!"))
-        .await?;
-
-    let g = get_guild_channel!(ctx, guild, item_channel);
-
-    g.send_message(ctx, |d| {
-        d.content("Removed all items.");
-
-        d
-    })
-    .await?;
-
-    Ok(())
+    Ok(Some(reply))
 }
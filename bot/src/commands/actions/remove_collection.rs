@@ -0,0 +1,136 @@
+use crate::{
+    commands::{
+        actions::remove::confirm,
+        checks::is_manager,
+        common::{get_channel, get_guild, get_guild_channel, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    printer, steam, Context, Error,
+};
+
+/// Remove all tracked members of a collection from the tracked items
+#[poise::command(
+    slash_command,
+    rename = "remove_collection",
+    check = "is_manager",
+    name_localized("de", "sammlung_entfernen"),
+    description_localized("de", "Entferne alle überwachten Einträge einer Sammlung")
+)]
+pub async fn remove_collection(
+    ctx: Context<'_>,
+    #[description = "The id of the collection to be removed"]
+    #[description_localized("de", "Die ID der zu entfernenden Sammlung")]
+    collection_id: u64,
+    #[description = "Only show which items would be removed"]
+    #[description_localized("de", "Nur anzeigen, welche Einträge entfernt würden")]
+    dry_run: Option<bool>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let item_channel = get_channel!(ctx, guild.id.0);
+
+    let collection = ok_or_respond!(
+        ctx,
+        steam::get_collection_ids(&ctx.data().pool, collection_id).await,
+        "An error occurred while fetching the collection."
+    );
+
+    let tracked = ok_or_respond!(
+        ctx,
+        db::items::get_subscribed_item_names(&ctx.data().pool, guild.id.0, None),
+        "An error occurred while fetching the subscriptions."
+    );
+
+    let to_remove: Vec<(String, u64)> = tracked
+        .into_iter()
+        .filter(|(_, id)| collection.contains(id))
+        .collect();
+
+    if to_remove.is_empty() {
+        ctx.say(tr!(
+            ctx,
+            "None of the {count} items in the collection are tracked.",
+            count = collection.len()
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let names = to_remove
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ");
+
+    let preview = tr!(
+        ctx,
+        "{count} of the {total} items in the collection are tracked: {names}",
+        count = to_remove.len(),
+        total = collection.len(),
+        names = names
+    );
+
+    if dry_run.unwrap_or(false) {
+        ctx.say(printer::truncate(&preview, 2000)).await?;
+        return Ok(());
+    }
+
+    let question = format!(
+        "\n{}",
+        tr!(
+            ctx,
+            "Are you sure you want to remove {count} items?",
+            count = to_remove.len()
+        )
+    );
+
+    let reply = match confirm(
+        ctx,
+        &format!(
+            "{}{}",
+            printer::truncate(&preview, 2000 - question.len()),
+            question
+        ),
+    )
+    .await?
+    {
+        Some(reply) => reply,
+        None => return Ok(()),
+    };
+
+    let ids: Vec<u64> = to_remove.iter().map(|(_, id)| *id).collect();
+
+    ok_or_respond!(
+        ctx,
+        db::subscriptions::remove_subscriptions(&ctx.data().pool, guild.id.0, &ids),
+        "An error occurred while removing the items."
+    );
+
+    reply
+        .edit(ctx, |b| {
+            b.components(|b| b)
+                .content(tr!(ctx, "Done! Use /undo_remove to restore them."))
+        })
+        .await?;
+
+    let g = get_guild_channel!(ctx, guild, item_channel);
+
+    g.send_message(ctx, |d| {
+        d.content(printer::truncate(
+            &tr!(
+                ctx,
+                "Removed {count} items of collection {collection}: {names}",
+                count = ids.len(),
+                collection = collection_id,
+                names = names
+            ),
+            2000,
+        ));
+
+        d
+    })
+    .await?;
+
+    Ok(())
+}
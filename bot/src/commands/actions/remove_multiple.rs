@@ -0,0 +1,275 @@
+use std::{collections::HashSet, time::Duration};
+
+use poise::serenity_prelude::{ButtonStyle, CreateComponents, InteractionResponseType};
+
+use crate::{
+    commands::{
+        actions::remove::confirm,
+        checks::is_manager,
+        common::{get_channel, get_guild, get_guild_channel, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    printer, Context, Error,
+};
+
+/// Discord allows at most 25 options per select menu
+const PAGE_SIZE: usize = 25;
+
+/// How long the selection keeps working
+const TIMEOUT: Duration = Duration::from_secs(60 * 10);
+
+/// Pick multiple items to remove from the tracked items
+#[poise::command(
+    slash_command,
+    rename = "remove_multiple",
+    check = "is_manager",
+    ephemeral,
+    name_localized("de", "mehrere_entfernen"),
+    description_localized(
+        "de",
+        "Wähle mehrere Einträge aus, die nicht mehr überwacht werden sollen"
+    )
+)]
+pub async fn remove_multiple(ctx: Context<'_>) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let item_channel = get_channel!(ctx, guild.id.0);
+
+    let mut items = ok_or_respond!(
+        ctx,
+        db::items::get_subscribed_item_names(&ctx.data().pool, guild.id.0, None),
+        "An error occurred while fetching the subscriptions."
+    );
+
+    if items.is_empty() {
+        ctx.say(tr!(ctx, "There are no tracked items.")).await?;
+        return Ok(());
+    }
+
+    items.sort_by_key(|(name, _)| name.to_lowercase());
+
+    let pages = items.len().div_ceil(PAGE_SIZE);
+    let mut page = 0;
+    let mut selected: HashSet<u64> = HashSet::new();
+
+    let reply = ctx
+        .send(|b| {
+            b.content(selection_message(ctx, &selected));
+            b.components(|c| {
+                *c = selection_components(ctx, &items, &selected, page, pages);
+                c
+            });
+            b
+        })
+        .await?;
+
+    let message = reply.message().await?;
+
+    loop {
+        let mc = match message
+            .await_component_interaction(ctx)
+            .author_id(ctx.author().id)
+            .timeout(TIMEOUT)
+            .await
+        {
+            Some(mc) => mc,
+            None => {
+                reply
+                    .edit(ctx, |b| {
+                        b.components(|c| c).content(tr!(
+                            ctx,
+                            ":warning: You didn't interact in time - please run the command again."
+                        ))
+                    })
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        match mc.data.custom_id.as_str() {
+            "remove_multiple:select" => {
+                // The menu only knows about the items of the current page
+                for (_, id) in items.iter().skip(page * PAGE_SIZE).take(PAGE_SIZE) {
+                    selected.remove(id);
+                }
+                selected.extend(mc.data.values.iter().filter_map(|v| v.parse::<u64>().ok()));
+            }
+            "remove_multiple:prev" => page = page.saturating_sub(1),
+            "remove_multiple:next" => page = (page + 1).min(pages - 1),
+            "remove_multiple:cancel" | "remove_multiple:remove" => {
+                let content = if mc.data.custom_id == "remove_multiple:cancel" {
+                    tr!(ctx, "Cancelled.")
+                } else {
+                    tr!(ctx, "Selected {count} items.", count = selected.len())
+                };
+
+                mc.create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::UpdateMessage);
+                    r.interaction_response_data(|d| {
+                        d.content(content);
+                        d.set_components(CreateComponents::default());
+                        d
+                    });
+                    r
+                })
+                .await?;
+
+                if mc.data.custom_id == "remove_multiple:cancel" {
+                    return Ok(());
+                }
+                break;
+            }
+            _ => continue,
+        }
+
+        mc.create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::UpdateMessage);
+            r.interaction_response_data(|d| {
+                d.content(selection_message(ctx, &selected));
+                d.set_components(selection_components(ctx, &items, &selected, page, pages));
+                d
+            });
+            r
+        })
+        .await?;
+    }
+
+    let reply = match confirm(
+        ctx,
+        &tr!(
+            ctx,
+            "Are you sure you want to remove {count} items?",
+            count = selected.len()
+        ),
+    )
+    .await?
+    {
+        Some(reply) => reply,
+        None => return Ok(()),
+    };
+
+    let ids: Vec<u64> = selected.iter().copied().collect();
+
+    ok_or_respond!(
+        ctx,
+        db::subscriptions::remove_subscriptions(&ctx.data().pool, guild.id.0, &ids),
+        "An error occurred while removing the items."
+    );
+
+    reply
+        .edit(ctx, |b| {
+            b.components(|b| b)
+                .content(tr!(ctx, "Done! Use /undo_remove to restore them."))
+        })
+        .await?;
+
+    let names: Vec<&str> = items
+        .iter()
+        .filter(|(_, id)| selected.contains(id))
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    let g = get_guild_channel!(ctx, guild, item_channel);
+
+    g.send_message(ctx, |d| {
+        d.content(printer::truncate(
+            &tr!(
+                ctx,
+                "Removed {count} items: {names}",
+                count = names.len(),
+                names = names.join(", ")
+            ),
+            2000,
+        ));
+
+        d
+    })
+    .await?;
+
+    Ok(())
+}
+
+fn selection_message(ctx: Context<'_>, selected: &HashSet<u64>) -> String {
+    tr!(
+        ctx,
+        "Select the items to remove. {count} items selected.",
+        count = selected.len()
+    )
+}
+
+fn selection_components(
+    ctx: Context<'_>,
+    items: &[(String, u64)],
+    selected: &HashSet<u64>,
+    page: usize,
+    pages: usize,
+) -> CreateComponents {
+    let page_items: Vec<&(String, u64)> = items
+        .iter()
+        .skip(page * PAGE_SIZE)
+        .take(PAGE_SIZE)
+        .collect();
+
+    let mut c = CreateComponents::default();
+
+    c.create_action_row(|r| {
+        r.create_select_menu(|m| {
+            m.custom_id("remove_multiple:select");
+            m.placeholder(tr!(
+                ctx,
+                "Items (page {page} of {pages})",
+                page = page + 1,
+                pages = pages
+            ));
+            m.min_values(0);
+            m.max_values(page_items.len() as u64);
+            m.options(|o| {
+                for (name, id) in &page_items {
+                    o.create_option(|opt| {
+                        opt.label(printer::truncate(name, 100));
+                        opt.value(id);
+                        opt.default_selection(selected.contains(id));
+                        opt
+                    });
+                }
+                o
+            });
+            m
+        });
+        r
+    });
+
+    c.create_action_row(|r| {
+        r.create_button(|b| {
+            b.style(ButtonStyle::Secondary);
+            b.label(tr!(ctx, "Prev"));
+            b.custom_id("remove_multiple:prev");
+            b.disabled(page == 0);
+            b
+        });
+        r.create_button(|b| {
+            b.style(ButtonStyle::Secondary);
+            b.label(tr!(ctx, "Next"));
+            b.custom_id("remove_multiple:next");
+            b.disabled(page + 1 >= pages);
+            b
+        });
+        r.create_button(|b| {
+            b.style(ButtonStyle::Danger);
+            b.label(tr!(ctx, "Remove selected"));
+            b.custom_id("remove_multiple:remove");
+            b.disabled(selected.is_empty());
+            b
+        });
+        r.create_button(|b| {
+            b.style(ButtonStyle::Secondary);
+            b.label(tr!(ctx, "Cancel"));
+            b.custom_id("remove_multiple:cancel");
+            b
+        });
+        r
+    });
+
+    c
+}
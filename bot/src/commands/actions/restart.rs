@@ -1,10 +1,21 @@
 use crate::{
-    commands::common::{get_guild, ok_or_respond},
-    db, Context, Error,
+    commands::{
+        checks::is_manager,
+        common::{get_guild, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    Context, Error,
 };
 
 /// Restarts your tracking job, immediately checking for any updates
-#[poise::command(slash_command, rename = "restart")]
+#[poise::command(
+    slash_command,
+    rename = "restart",
+    check = "is_manager",
+    name_localized("de", "neustarten"),
+    description_localized("de", "Startet die Überwachung neu und sucht sofort nach Updates")
+)]
 pub async fn restart(ctx: Context<'_>) -> Result<(), Error> {
     let guild = get_guild!(ctx);
 
@@ -15,7 +26,7 @@ pub async fn restart(ctx: Context<'_>) -> Result<(), Error> {
     );
 
     if schedule.is_none() {
-        ctx.say("Please set a schedule first.").await?;
+        ctx.say(tr!(ctx, "Please set a schedule first.")).await?;
         return Ok(());
     }
 
@@ -25,6 +36,6 @@ pub async fn restart(ctx: Context<'_>) -> Result<(), Error> {
         "An error occurred while restarting the tracking job."
     );
 
-    ctx.say("Restarted tracking job.").await?;
+    ctx.say(tr!(ctx, "Restarted tracking job.")).await?;
     Ok(())
 }
@@ -1,61 +1,213 @@
 use std::time;
 
 use crate::{
-    commands::common::*,
-    printer::{send_in_chunks, send_in_one},
+    commands::{autocomplete::autocomplete_group, common::*},
+    i18n::{self, tr},
+    printer::{self, item_to_embed, send_in_chunks, send_in_one, Destination, MessageOptions},
     Context, Error,
 };
 
 /// List all changes since a specified date
-#[poise::command(slash_command, rename = "changes_since")]
+#[poise::command(
+    slash_command,
+    rename = "changes_since",
+    name_localized("de", "änderungen_seit"),
+    description_localized("de", "Zeige alle Änderungen seit einem bestimmten Zeitpunkt")
+)]
 pub async fn changes_since(
     ctx: Context<'_>,
-    #[description = "The since. Format: mm/dd/yy"] time_str: String,
+    #[description = "The start, e.g. 3d, 2 weeks, last restart or a date like mm/dd/yy"]
+    #[description_localized(
+        "de",
+        "Der Beginn, z.B. 3d, 2 weeks, last restart oder ein Datum wie mm/dd/yy"
+    )]
+    from: String,
+    #[description = "The end, e.g. 1d or a date like mm/dd/yy. Defaults to now"]
+    #[description_localized(
+        "de",
+        "Das Ende, z.B. 1d oder ein Datum wie mm/dd/yy. Standardmäßig jetzt"
+    )]
+    to: Option<String>,
+    #[autocomplete = "autocomplete_group"]
+    #[description = "Only show changes of items in this group"]
+    #[description_localized("de", "Nur Änderungen von Einträgen dieser Gruppe zeigen")]
+    group: Option<String>,
+    #[description = "Only show the changes to you instead of posting them in the update channel"]
+    #[description_localized(
+        "de",
+        "Die Änderungen nur dir zeigen, statt sie im Update-Kanal zu posten"
+    )]
+    ephemeral: Option<bool>,
 ) -> Result<(), Error> {
     let guild = get_guild!(ctx);
 
-    let item_channel = get_channel!(ctx, guild.id.0);
+    let now = time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)?
+        .as_secs();
 
-    let since = ok_or_respond!(
+    let tz = ok_or_respond!(
         ctx,
-        dateparser::parse(&time_str),
-        "Invalid date format. Format: mm/dd/yy"
+        crate::db::servers::get_timezone(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the timezone."
     );
 
-    let timestamp = ok_or_respond!(
+    let last_restart = ok_or_respond!(
         ctx,
-        u64::try_from(since.timestamp()),
-        "Date too far in the past"
+        crate::db::servers::get_last_restart(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the last restart."
     );
 
-    if timestamp
-        > time::SystemTime::now()
-            .duration_since(time::UNIX_EPOCH)?
-            .as_secs()
-    {
-        ctx.say(
-            "Whoa there bucko! I can't track the future! Be aware that all my dates are in UTC!",
-        )
+    let invalid = tr!(
+        ctx,
+        "Invalid time. Use something like 3d, 2 weeks, last restart or a date like mm/dd/yy. Dates are in {timezone}.",
+        timezone = tz.name()
+    );
+
+    let since = match parse_time(&from, &tz, now, last_restart) {
+        Some(since) => since,
+        None => {
+            ctx.say(invalid).await?;
+            return Ok(());
+        }
+    };
+
+    let until = match to.map(|to| parse_time(&to, &tz, now, last_restart)) {
+        Some(Some(until)) => until,
+        Some(None) => {
+            ctx.say(invalid).await?;
+            return Ok(());
+        }
+        None => now,
+    };
+
+    if since > now {
+        ctx.say(tr!(
+            ctx,
+            "Whoa there bucko! I can't track the future! Be aware that all my dates are in {timezone}!",
+            timezone = tz.name()
+        ))
         .await?;
         return Ok(());
     }
 
-    let changes = ok_or_respond!(
+    if since >= until {
+        ctx.say(tr!(ctx, "The start has to be before the end."))
+            .await?;
+        return Ok(());
+    }
+
+    let mut changes = ok_or_respond!(
         ctx,
-        crate::db::subscriptions::get_changes_since(&ctx.data().pool, guild.id.0, timestamp).await,
+        crate::db::subscriptions::get_changes_since(&ctx.data().pool, guild.id.0, since, until)
+            .await,
         "An error occurred while fetching the changes."
     );
 
-    let g = get_guild_channel!(ctx, guild, item_channel);
+    if let Some(group) = group {
+        let group_id = get_group!(ctx, guild.id.0, group);
+
+        let members = ok_or_respond!(
+            ctx,
+            crate::db::groups::get_group_items(&ctx.data().pool, group_id),
+            "An error occurred while fetching the group."
+        );
+
+        changes.retain(|(info, _)| members.contains(&info.id));
+    }
+
+    let groups = ok_or_respond!(
+        ctx,
+        crate::db::groups::get_item_groups(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the groups."
+    );
+
+    let templates = ok_or_respond!(
+        ctx,
+        crate::db::servers::get_templates(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the templates."
+    );
+
+    let provenance = ok_or_respond!(
+        ctx,
+        printer::get_provenance(&ctx.data().pool, &ctx, guild.id.0).await,
+        "An error occurred while fetching the subscriptions."
+    );
 
     if changes.is_empty() {
-        ctx.say("No changes since then").await?;
-    } else if changes.len() > 5 {
-        ctx.say("Sending").await?;
-        send_in_chunks("Changes since then:", &g, &ctx, &changes).await?;
+        ctx.send(|m| {
+            m.content(tr!(ctx, "No changes in that time."));
+            m.ephemeral(ephemeral.unwrap_or(false));
+            m
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let language = i18n::guild_language(&ctx.data().pool, Some(guild.id.0));
+
+    let msg = tr!(
+        ctx,
+        "Changes between <t:{since}:f> and <t:{until}:f>:",
+        since = since,
+        until = until
+    );
+
+    if ephemeral.unwrap_or(false) {
+        let parts = changes.len().div_ceil(5);
+
+        for (curr, chunk) in changes.chunks(5).enumerate() {
+            ctx.send(|m| {
+                m.content(format!(
+                    "{}\n{}",
+                    msg,
+                    tr!(ctx, "Part {part}/{parts}", part = curr + 1, parts = parts)
+                ));
+                for (item_info, note) in chunk {
+                    m.embed(|e| {
+                        *e = item_to_embed(
+                            item_info,
+                            note,
+                            templates.item.as_deref(),
+                            changes.len(),
+                            groups
+                                .get(&item_info.id)
+                                .map(Vec::as_slice)
+                                .unwrap_or_default(),
+                            provenance.get(&item_info.id),
+                            language,
+                        );
+                        e
+                    });
+                }
+                m.ephemeral(true);
+                m
+            })
+            .await?;
+        }
+
+        return Ok(());
+    }
+
+    let destination = ok_or_respond!(
+        ctx,
+        Destination::of_guild(&ctx.data().pool, &ctx.serenity_context().http, guild.id.0).await,
+        "Could not reach the update channel. Please set an update channel first."
+    );
+
+    let options = MessageOptions {
+        item_template: templates.item.as_deref(),
+        groups: Some(&groups),
+        provenance: Some(&provenance),
+        language,
+        ..Default::default()
+    };
+
+    ctx.say(tr!(ctx, "Sending")).await?;
+
+    if changes.len() > 5 {
+        send_in_chunks(&msg, &destination, &ctx, &changes, options).await?;
     } else {
-        ctx.say("Sending").await?;
-        send_in_one("Changes since then:", &g, &ctx, &changes).await?;
+        send_in_one(&msg, &destination, &ctx, &changes, options).await?;
     }
 
     Ok(())
@@ -0,0 +1,105 @@
+use crate::{
+    commands::{
+        checks::is_contributor,
+        common::{get_by_name, get_guild, ok_or_respond},
+    },
+    db::{self, subscriptions::TRASH_RETENTION},
+    i18n::tr,
+    printer, Context, Error,
+};
+
+/// List the removed items that can still be restored
+#[poise::command(
+    slash_command,
+    rename = "trash",
+    ephemeral,
+    name_localized("de", "papierkorb"),
+    description_localized(
+        "de",
+        "Zeige die entfernten Einträge, die noch wiederhergestellt werden können"
+    )
+)]
+pub async fn trash(ctx: Context<'_>) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let trash = ok_or_respond!(
+        ctx,
+        db::subscriptions::get_trash(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the trash."
+    );
+
+    if trash.is_empty() {
+        ctx.say(tr!(ctx, "The trash is empty.")).await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = trash
+        .iter()
+        .map(|(name, id, removed_at)| {
+            tr!(
+                ctx,
+                "{name} ({id}): removed <t:{removed}:R>, deleted <t:{deleted}:R>",
+                name = name,
+                id = id,
+                removed = removed_at,
+                deleted = removed_at + TRASH_RETENTION
+            )
+        })
+        .collect();
+
+    let header = tr!(ctx, "Removed items, restore them with /undo_remove:");
+
+    for message in printer::split_message(&header, &lines) {
+        ctx.say(message).await?;
+    }
+
+    Ok(())
+}
+
+/// Restore the items of the last removal, or a single removed item
+#[poise::command(
+    slash_command,
+    rename = "undo_remove",
+    check = "is_contributor",
+    name_localized("de", "entfernen_rückgängig"),
+    description_localized(
+        "de",
+        "Stelle die zuletzt entfernten Einträge oder einen einzelnen Eintrag wieder her"
+    )
+)]
+pub async fn undo_remove(
+    ctx: Context<'_>,
+    #[description = "The id or the name of a removed item to restore"]
+    #[description_localized("de", "Die ID oder der Name eines entfernten Eintrags")]
+    item: Option<String>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let item_id = match item {
+        Some(item) => Some(get_by_name!(ctx, item).id),
+        None => None,
+    };
+
+    let restored = ok_or_respond!(
+        ctx,
+        db::subscriptions::restore_subscriptions(&ctx.data().pool, guild.id.0, item_id),
+        "An error occurred while restoring the items."
+    );
+
+    match restored {
+        0 => {
+            ctx.say(tr!(
+                ctx,
+                "There is nothing to restore. See /trash for the removed items."
+            ))
+            .await?
+        }
+        1 => ctx.say(tr!(ctx, "Restored 1 item.")).await?,
+        n => {
+            ctx.say(tr!(ctx, "Restored {count} items.", count = n))
+                .await?
+        }
+    };
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::{
+    commands::{
+        autocomplete::autocomplete_name,
+        common::{get_by_name, get_guild, ok_or_respond},
+    },
+    db,
+    i18n::{self, tr},
+    printer, Context, Error,
+};
+
+/// Get a DM whenever an item updates
+#[poise::command(
+    slash_command,
+    rename = "watch",
+    ephemeral,
+    name_localized("de", "beobachten"),
+    description_localized("de", "Erhalte eine DM, wenn ein Eintrag aktualisiert wird")
+)]
+pub async fn watch(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "The id or the name of the item"]
+    #[description_localized("de", "Die ID oder der Name des Eintrags")]
+    item: String,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let item_info = get_by_name!(ctx, item);
+
+    // Only items tracked by a guild are checked for updates
+    if !ok_or_respond!(
+        ctx,
+        db::subscriptions::check_subscription(&ctx.data().pool, guild.id.0, item_info.id),
+        "An error occurred while checking the item."
+    ) {
+        ctx.say(tr!(ctx, "This item is not tracked by this server."))
+            .await?;
+        return Ok(());
+    }
+
+    ok_or_respond!(
+        ctx,
+        db::watches::add_watch(
+            &ctx.data().pool,
+            ctx.author().id.0,
+            item_info.id,
+            i18n::guild_language(&ctx.data().pool, Some(guild.id.0))
+        ),
+        "An error occurred while adding the item to your watchlist."
+    );
+
+    ctx.say(tr!(
+        ctx,
+        "Added {name} to your watchlist. Make sure you allow DMs from this server.",
+        name = item_info.name
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Stop getting DMs when an item updates
+#[poise::command(
+    slash_command,
+    rename = "unwatch",
+    ephemeral,
+    name_localized("de", "nicht_mehr_beobachten"),
+    description_localized("de", "Erhalte keine DMs mehr, wenn ein Eintrag aktualisiert wird")
+)]
+pub async fn unwatch(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "The id or the name of the item"]
+    #[description_localized("de", "Die ID oder der Name des Eintrags")]
+    item: String,
+) -> Result<(), Error> {
+    let item_info = get_by_name!(ctx, item);
+
+    ok_or_respond!(
+        ctx,
+        db::watches::remove_watch(&ctx.data().pool, ctx.author().id.0, item_info.id),
+        "An error occurred while removing the item from your watchlist."
+    );
+
+    ctx.say(tr!(
+        ctx,
+        "Removed {name} from your watchlist.",
+        name = item_info.name
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// List the items you get DMs for
+#[poise::command(
+    slash_command,
+    rename = "watchlist",
+    ephemeral,
+    name_localized("de", "beobachtungsliste"),
+    description_localized("de", "Zeige die Einträge, für die du DMs erhältst")
+)]
+pub async fn watchlist(ctx: Context<'_>) -> Result<(), Error> {
+    let items = ok_or_respond!(
+        ctx,
+        db::watches::get_watched_items(&ctx.data().pool, ctx.author().id.0),
+        "An error occurred while fetching your watchlist."
+    );
+
+    if items.is_empty() {
+        ctx.say(tr!(ctx, "Your watchlist is empty.")).await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = items
+        .iter()
+        .map(|(name, id)| {
+            format!(
+                "{}: <https://steamcommunity.com/sharedfiles/filedetails/?id={}>",
+                name, id
+            )
+        })
+        .collect();
+
+    for message in printer::split_message(&tr!(ctx, "Your watchlist:"), &lines) {
+        ctx.say(message).await?;
+    }
+
+    Ok(())
+}
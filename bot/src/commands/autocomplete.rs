@@ -47,6 +47,55 @@ pub async fn autocomplete_name(
     res.into_iter().map(to_choice)
 }
 
+pub async fn autocomplete_group(
+    ctx: Context<'_>,
+    partial: &str,
+) -> impl Iterator<Item = poise::AutocompleteChoice<String>> {
+    let guild = match ctx.guild() {
+        Some(g) => g,
+        None => return vec![].into_iter(),
+    };
+
+    match crate::db::groups::get_group_names(
+        &ctx.data().pool,
+        guild.id.0,
+        Some(partial.to_string()),
+    ) {
+        Ok(names) => names
+            .into_iter()
+            .map(|name| poise::AutocompleteChoice {
+                name: name.clone(),
+                value: name,
+            })
+            .collect::<Vec<_>>()
+            .into_iter(),
+        Err(e) => {
+            error!("Error getting group names: {:?}", e);
+            vec![].into_iter()
+        }
+    }
+}
+
+pub async fn autocomplete_timezone(
+    _ctx: Context<'_>,
+    partial: &str,
+) -> impl Iterator<Item = poise::AutocompleteChoice<String>> {
+    let partial = partial.to_lowercase();
+
+    chrono_tz::TZ_VARIANTS
+        .iter()
+        .map(|tz| tz.name())
+        .filter(|name| name.to_lowercase().contains(&partial))
+        // Discord shows at most 25 choices
+        .take(25)
+        .map(|name| poise::AutocompleteChoice {
+            name: name.to_string(),
+            value: name.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
 #[derive(Clone)]
 struct NameCache {
     #[allow(clippy::type_complexity)]
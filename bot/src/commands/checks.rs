@@ -0,0 +1,183 @@
+use mysql::Pool;
+use poise::serenity_prelude::{Member, RoleId};
+
+use crate::{db, db::PermissionLevel, Context, Error};
+
+/// Checks the bot-side permissions of the author of a command.
+async fn has_level(ctx: Context<'_>, level: PermissionLevel) -> Result<bool, Error> {
+    let guild_id = match ctx.guild_id() {
+        Some(g) => g.0,
+        // Commands outside of a guild are rejected by the commands themselves
+        None => return Ok(true),
+    };
+
+    let member = match ctx.author_member().await {
+        Some(m) => m,
+        None => return Ok(false),
+    };
+
+    let manage_guild = member.permissions(ctx.serenity_context())?.manage_guild();
+    let (manager, contributor) = db::servers::get_permission_roles(&ctx.data().pool, guild_id)?;
+
+    Ok(allowed(
+        level,
+        manage_guild,
+        &member.roles,
+        manager,
+        contributor,
+    ))
+}
+
+/// The same check for the member that pressed a button, whose permissions come with the interaction
+pub fn member_has_level(
+    pool: &Pool,
+    guild_id: u64,
+    member: &Member,
+    level: PermissionLevel,
+) -> Result<bool, Error> {
+    let manage_guild = member.permissions.is_some_and(|p| p.manage_guild());
+    let (manager, contributor) = db::servers::get_permission_roles(pool, guild_id)?;
+
+    Ok(allowed(
+        level,
+        manage_guild,
+        &member.roles,
+        manager,
+        contributor,
+    ))
+}
+
+/// Members allowed to manage the server always pass. A level without a role is open to everyone,
+/// so only the overrides of the integration settings apply.
+fn allowed(
+    level: PermissionLevel,
+    manage_guild: bool,
+    roles: &[RoleId],
+    manager: Option<u64>,
+    contributor: Option<u64>,
+) -> bool {
+    if manage_guild {
+        return true;
+    }
+
+    let has_role = |role: Option<u64>| role.map(|r| roles.contains(&RoleId(r)));
+
+    // Managers may do everything contributors can
+    let allowed = match level {
+        PermissionLevel::Manager => has_role(manager),
+        PermissionLevel::Contributor => match (has_role(manager), has_role(contributor)) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (_, None) => None,
+            _ => Some(false),
+        },
+    };
+
+    allowed.unwrap_or(true)
+}
+
+/// Settings and destructive commands
+pub async fn is_manager(ctx: Context<'_>) -> Result<bool, Error> {
+    has_level(ctx, PermissionLevel::Manager).await
+}
+
+/// Adding items and editing notes
+pub async fn is_contributor(ctx: Context<'_>) -> Result<bool, Error> {
+    has_level(ctx, PermissionLevel::Contributor).await
+}
+
+/// Without a manager role, only members allowed to manage the server may hand out permissions
+pub async fn can_manage_permissions(ctx: Context<'_>) -> Result<bool, Error> {
+    let guild_id = match ctx.guild_id() {
+        Some(g) => g.0,
+        None => return Ok(true),
+    };
+
+    let (manager, _) = db::servers::get_permission_roles(&ctx.data().pool, guild_id)?;
+
+    if manager.is_some() {
+        return is_manager(ctx).await;
+    }
+
+    match ctx.author_member().await {
+        Some(member) => Ok(member.permissions(ctx.serenity_context())?.manage_guild()),
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANAGER: u64 = 1;
+    const CONTRIBUTOR: u64 = 2;
+
+    #[test]
+    fn levels_without_roles_are_open() {
+        for level in [PermissionLevel::Manager, PermissionLevel::Contributor] {
+            assert!(allowed(level, false, &[], None, None));
+        }
+    }
+
+    #[test]
+    fn manage_guild_always_passes() {
+        for level in [PermissionLevel::Manager, PermissionLevel::Contributor] {
+            assert!(allowed(level, true, &[], Some(MANAGER), Some(CONTRIBUTOR)));
+        }
+    }
+
+    #[test]
+    fn manager_role_is_required_once_set() {
+        let level = PermissionLevel::Manager;
+
+        assert!(allowed(
+            level,
+            false,
+            &[RoleId(MANAGER)],
+            Some(MANAGER),
+            None
+        ));
+        assert!(!allowed(level, false, &[], Some(MANAGER), None));
+        assert!(!allowed(
+            level,
+            false,
+            &[RoleId(CONTRIBUTOR)],
+            Some(MANAGER),
+            Some(CONTRIBUTOR)
+        ));
+    }
+
+    #[test]
+    fn managers_are_contributors() {
+        let level = PermissionLevel::Contributor;
+
+        assert!(allowed(
+            level,
+            false,
+            &[RoleId(MANAGER)],
+            Some(MANAGER),
+            Some(CONTRIBUTOR)
+        ));
+        assert!(allowed(
+            level,
+            false,
+            &[RoleId(CONTRIBUTOR)],
+            Some(MANAGER),
+            Some(CONTRIBUTOR)
+        ));
+        assert!(!allowed(
+            level,
+            false,
+            &[],
+            Some(MANAGER),
+            Some(CONTRIBUTOR)
+        ));
+    }
+
+    #[test]
+    fn contributors_stay_open_without_a_contributor_role() {
+        let level = PermissionLevel::Contributor;
+
+        assert!(allowed(level, false, &[], Some(MANAGER), None));
+        assert!(!allowed(level, false, &[], None, Some(CONTRIBUTOR)));
+    }
+}
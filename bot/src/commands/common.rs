@@ -1,10 +1,64 @@
+use chrono::NaiveTime;
+use chrono_tz::Tz;
+
+/// Parses durations like `3d`, `12h` or `2 weeks` into seconds
+pub fn parse_duration(s: &str) -> Option<u64> {
+    let s = s.trim().to_lowercase();
+    let split = s.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = s.split_at(split);
+
+    let amount: u64 = amount.parse().ok()?;
+
+    let unit = match unit.trim() {
+        "m" | "min" | "mins" | "minute" | "minutes" => 60,
+        "h" | "hour" | "hours" => 60 * 60,
+        "d" | "day" | "days" => 60 * 60 * 24,
+        "w" | "week" | "weeks" => 60 * 60 * 24 * 7,
+        _ => return None,
+    };
+
+    amount.checked_mul(unit)
+}
+
+/// Parses a point in time: relative like `3d` or `2 weeks ago`, `last restart`, `now`
+/// or a date, which is read in the timezone of the guild
+pub fn parse_time(input: &str, tz: &Tz, now: u64, last_restart: Option<u64>) -> Option<u64> {
+    let trimmed = input.trim();
+    let lower = trimmed.to_lowercase();
+    let lower = lower.strip_prefix("since ").unwrap_or(&lower);
+    let lower = lower.strip_suffix(" ago").unwrap_or(lower).trim();
+
+    match lower {
+        "now" => return Some(now),
+        "last restart" => return last_restart,
+        _ => {}
+    }
+
+    if let Some(secs) = parse_duration(lower) {
+        return now.checked_sub(secs);
+    }
+
+    parse_date(trimmed, tz)
+}
+
+/// Parses a date in the timezone of the guild, dates without a time are read as midnight
+pub fn parse_date(input: &str, tz: &Tz) -> Option<u64> {
+    let midnight = NaiveTime::from_hms_opt(0, 0, 0).unwrap_or_default();
+    let date = dateparser::parse_with(input.trim(), tz, midnight).ok()?;
+
+    u64::try_from(date.timestamp()).ok()
+}
+
 macro_rules! get_guild {
     ($ctx:expr) => {
         match $ctx.guild() {
             Some(g) => g,
             None => {
-                $ctx.say("This command can only be used in a guild.")
-                    .await?;
+                $ctx.say(crate::i18n::tr!(
+                    $ctx,
+                    "This command can only be used in a guild."
+                ))
+                .await?;
                 return Ok(());
             }
         }
@@ -17,13 +71,20 @@ macro_rules! get_channel {
             Ok(c) => match c {
                 Some(c) => c,
                 None => {
-                    $ctx.say("Please set an update channel first.").await?;
+                    $ctx.say(crate::i18n::tr!(
+                        $ctx,
+                        "Please set an update channel first, or run /setup."
+                    ))
+                    .await?;
                     return Ok(());
                 }
             },
             Err(_) => {
-                $ctx.say("An error occurred while fetching the update channel.")
-                    .await?;
+                $ctx.say(crate::i18n::tr!(
+                    $ctx,
+                    "An error occurred while fetching the update channel."
+                ))
+                .await?;
                 return Ok(());
             }
         }
@@ -35,8 +96,11 @@ macro_rules! get_guild_channel {
         match crate::commands::actions::get_guild_channel(&$guild, $item_channel) {
             Some(g) => g,
             None => {
-                $ctx.say("The update channel is no longer available")
-                    .await?;
+                $ctx.say(crate::i18n::tr!(
+                    $ctx,
+                    "The update channel is no longer available"
+                ))
+                .await?;
                 return Ok(());
             }
         }
@@ -49,7 +113,7 @@ macro_rules! ok_or_respond {
             Ok(val) => val,
             Err(e) => {
                 tracing::error!("Error while executing command: {e}");
-                $ctx.say($msg).await?;
+                $ctx.say(crate::i18n::tr!($ctx, $msg)).await?;
                 return Ok(());
             }
         }
@@ -77,8 +141,90 @@ macro_rules! get_by_name {
     };
 }
 
+macro_rules! get_group {
+    ($ctx:expr, $guild_id:expr, $name:expr) => {
+        match ok_or_respond!(
+            $ctx,
+            crate::db::groups::get_group_id(&$ctx.data().pool, $guild_id, &$name),
+            "An error occurred while fetching the group."
+        ) {
+            Some(id) => id,
+            None => {
+                $ctx.say(crate::i18n::tr!(
+                    $ctx,
+                    "There is no group called {name}.",
+                    name = $name
+                ))
+                .await?;
+                return Ok(());
+            }
+        }
+    };
+}
+
 pub(crate) use get_by_name;
 pub(crate) use get_channel;
+pub(crate) use get_group;
 pub(crate) use get_guild;
 pub(crate) use get_guild_channel;
 pub(crate) use ok_or_respond;
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn durations_accept_short_and_long_units() {
+        assert_eq!(parse_duration("3d"), Some(3 * 24 * 60 * 60));
+        assert_eq!(parse_duration("12h"), Some(12 * 60 * 60));
+        assert_eq!(parse_duration(" 2 Weeks "), Some(2 * 7 * 24 * 60 * 60));
+        assert_eq!(parse_duration("30 min"), Some(30 * 60));
+        assert_eq!(parse_duration("3"), None);
+        assert_eq!(parse_duration("d"), None);
+        assert_eq!(parse_duration("3 years"), None);
+        assert_eq!(parse_duration("18446744073709551615w"), None);
+    }
+
+    #[test]
+    fn times_are_read_relative_to_now() {
+        let now = 1_000_000;
+        let tz = chrono_tz::UTC;
+
+        assert_eq!(parse_time("now", &tz, now, None), Some(now));
+        assert_eq!(parse_time("3h", &tz, now, None), Some(now - 3 * 60 * 60));
+        assert_eq!(
+            parse_time("since 2 days ago", &tz, now, None),
+            Some(now - 2 * 24 * 60 * 60)
+        );
+        assert_eq!(parse_time("Last Restart", &tz, now, Some(42)), Some(42));
+        assert_eq!(parse_time("last restart", &tz, now, None), None);
+        assert_eq!(parse_time("20 weeks", &tz, now, None), None);
+    }
+
+    #[test]
+    fn dates_are_read_in_the_timezone_of_the_guild() {
+        let berlin = chrono_tz::Europe::Berlin;
+
+        assert_eq!(
+            parse_date("2024-01-10", &chrono_tz::UTC),
+            Some(
+                chrono_tz::UTC
+                    .with_ymd_and_hms(2024, 1, 10, 0, 0, 0)
+                    .unwrap()
+                    .timestamp() as u64
+            )
+        );
+        assert_eq!(
+            parse_date("2024-01-10", &berlin),
+            Some(
+                berlin
+                    .with_ymd_and_hms(2024, 1, 10, 0, 0, 0)
+                    .unwrap()
+                    .timestamp() as u64
+            )
+        );
+        assert_eq!(parse_date("not a date", &berlin), None);
+    }
+}
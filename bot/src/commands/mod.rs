@@ -1,4 +1,5 @@
 pub mod actions;
 pub mod autocomplete;
+pub mod checks;
 pub mod common;
 pub mod settings;
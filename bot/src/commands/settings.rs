@@ -1,3 +1,14 @@
 pub mod info;
+pub mod permissions;
 pub mod register_channel;
+pub mod set_check_interval;
+pub mod set_escalation;
+pub mod set_hook;
+pub mod set_language;
+pub mod set_mention;
+pub mod set_notification_mode;
 pub mod set_schedule;
+pub mod set_template;
+pub mod set_timezone;
+pub mod set_webhook;
+pub mod setup;
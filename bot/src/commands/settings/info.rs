@@ -1,10 +1,17 @@
 use crate::{
     commands::common::{get_guild, ok_or_respond},
-    db, Context, Error,
+    db,
+    i18n::tr,
+    Context, Error,
 };
 
 /// Get info about your bot
-#[poise::command(slash_command, rename = "info")]
+#[poise::command(
+    slash_command,
+    rename = "info",
+    name_localized("de", "info"),
+    description_localized("de", "Zeige Informationen über deinen Bot")
+)]
 pub async fn get_info(ctx: Context<'_>) -> Result<(), Error> {
     let guild = get_guild!(ctx);
 
@@ -22,16 +29,46 @@ pub async fn get_info(ctx: Context<'_>) -> Result<(), Error> {
         "An error occurred while fetching the last update."
     );
 
+    let mode = ok_or_respond!(
+        ctx,
+        db::servers::get_notification_mode(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the notification mode."
+    );
+
+    let tz = ok_or_respond!(
+        ctx,
+        db::servers::get_timezone(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the timezone."
+    );
+
     let mut msg = String::new();
 
-    msg.push_str(&format!("Your server is subscribed to {count} mods\n"));
-    let status = if is_running { "running" } else { "not running" };
+    msg.push_str(&tr!(
+        ctx,
+        "Your server is subscribed to {count} mods\n",
+        count = count
+    ));
+    let status = if is_running {
+        tr!(ctx, "running")
+    } else {
+        tr!(ctx, "not running")
+    };
     let time = match last_update {
         Some(last_update) => format!("<t:{last_update}:R>"),
-        None => "never".to_string(),
+        None => tr!(ctx, "never"),
     };
-    msg.push_str(&format!("The tracking job is {status}\n"));
-    msg.push_str(&format!("The last update was: {time}\n"));
+    msg.push_str(&tr!(ctx, "The tracking job is {status}\n", status = status));
+    msg.push_str(&tr!(ctx, "The last update was: {time}\n", time = time));
+    msg.push_str(&tr!(
+        ctx,
+        "Updates are posted: {mode}\n",
+        mode = tr!(ctx, mode.name())
+    ));
+    msg.push_str(&tr!(
+        ctx,
+        "Dates are read in: {timezone}\n",
+        timezone = tz.name()
+    ));
 
     ctx.say(msg).await?;
 
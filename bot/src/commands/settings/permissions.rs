@@ -0,0 +1,105 @@
+use poise::serenity_prelude as serenity;
+
+use crate::{
+    commands::{
+        checks::can_manage_permissions,
+        common::{get_guild, ok_or_respond},
+    },
+    db::{self, PermissionLevel},
+    i18n::tr,
+    Context, Error,
+};
+
+/// Show who may use the bot, or set the role of a permission level
+#[poise::command(
+    slash_command,
+    rename = "permissions",
+    check = "can_manage_permissions",
+    ephemeral,
+    name_localized("de", "berechtigungen"),
+    description_localized(
+        "de",
+        "Zeige, wer den Bot verwenden darf, oder lege die Rolle einer Berechtigungsstufe fest"
+    )
+)]
+pub async fn permissions(
+    ctx: Context<'_>,
+    #[description = "The level to change. Leave empty to show the current roles"]
+    #[description_localized(
+        "de",
+        "Die zu ändernde Stufe. Leer lassen, um die aktuellen Rollen zu zeigen"
+    )]
+    level: Option<PermissionLevel>,
+    #[description = "The role of the level. Leave empty to open the level to everyone"]
+    #[description_localized(
+        "de",
+        "Die Rolle der Stufe. Leer lassen, um die Stufe für alle zu öffnen"
+    )]
+    role: Option<serenity::Role>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let level = match level {
+        Some(level) => level,
+        None => {
+            let (manager, contributor) = ok_or_respond!(
+                ctx,
+                db::servers::get_permission_roles(&ctx.data().pool, guild.id.0),
+                "An error occurred while fetching the permissions."
+            );
+
+            let describe = |role: Option<u64>| match role {
+                Some(id) => format!("<@&{}>", id),
+                None => tr!(ctx, "everyone"),
+            };
+
+            ctx.send(|m| {
+                m.content(tr!(
+                    ctx,
+                    "Managers: {manager}\nContributors: {contributor}\nMembers who can manage the server can always use every command.",
+                    manager = describe(manager),
+                    contributor = describe(contributor)
+                ));
+                m.allowed_mentions(|am| am.empty_parse());
+                m
+            })
+            .await?;
+            return Ok(());
+        }
+    };
+
+    ok_or_respond!(
+        ctx,
+        db::servers::set_permission_role(
+            &ctx.data().pool,
+            guild.id.0,
+            level,
+            role.as_ref().map(|r| r.id.0)
+        ),
+        "An error occurred while updating the permissions."
+    );
+
+    let level_name = tr!(ctx, level.name());
+
+    match role {
+        Some(role) => {
+            ctx.say(tr!(
+                ctx,
+                "{role} is now the {level} role.",
+                role = role.name,
+                level = level_name
+            ))
+            .await?
+        }
+        None => {
+            ctx.say(tr!(
+                ctx,
+                "Everyone may now use the {level} commands.",
+                level = level_name
+            ))
+            .await?
+        }
+    };
+
+    Ok(())
+}
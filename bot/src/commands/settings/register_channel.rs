@@ -1,13 +1,26 @@
 use crate::{
-    commands::common::{get_guild, ok_or_respond},
-    db, Context, Error,
+    commands::{
+        checks::is_manager,
+        common::{get_guild, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    Context, Error,
 };
 
 /// Set the channel where the bot will send updates to
-#[poise::command(slash_command, rename = "register_channel")]
+#[poise::command(
+    slash_command,
+    rename = "register_channel",
+    check = "is_manager",
+    name_localized("de", "kanal_registrieren"),
+    description_localized("de", "Lege den Kanal fest, in den der Bot Updates postet")
+)]
 pub async fn register_channel(
     ctx: Context<'_>,
-    #[description = "Channel Id for update broadcast"] channel_id: Option<u64>,
+    #[description = "Channel Id for update broadcast"]
+    #[description_localized("de", "Kanal-ID für die Updates")]
+    channel_id: Option<u64>,
 ) -> Result<(), Error> {
     let guild = get_guild!(ctx);
 
@@ -30,7 +43,8 @@ pub async fn register_channel(
     };
 
     if !guild.channels.iter().any(|c| c.0 .0 == channel_id) {
-        ctx.say("Please provide a valid channel id.").await?;
+        ctx.say(tr!(ctx, "Please provide a valid channel id."))
+            .await?;
         return Ok(());
     }
 
@@ -40,7 +54,7 @@ pub async fn register_channel(
         "An error occurred while updating the channel."
     );
 
-    ctx.say("Update channel set.").await?;
+    ctx.say(tr!(ctx, "Update channel set.")).await?;
 
     Ok(())
 }
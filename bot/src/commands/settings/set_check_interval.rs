@@ -0,0 +1,86 @@
+use crate::{
+    commands::{
+        autocomplete::autocomplete_name,
+        checks::is_manager,
+        common::{get_by_name, get_guild, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    Context, Error,
+};
+
+/// Check an item more or less often than the rest. Leave minutes empty to use the server schedule
+#[poise::command(
+    slash_command,
+    rename = "set_check_interval",
+    check = "is_manager",
+    name_localized("de", "prüfintervall_festlegen"),
+    description_localized(
+        "de",
+        "Prüfe einen Eintrag öfter oder seltener als den Rest. Ohne Minuten gilt der Zeitplan"
+    )
+)]
+pub async fn set_check_interval(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "The id or the name of the item"]
+    #[description_localized("de", "Die ID oder der Name des Eintrags")]
+    item: String,
+    #[description = "The item will be checked every x minutes"]
+    #[description_localized("de", "Der Eintrag wird alle x Minuten geprüft")]
+    #[min = 5]
+    minutes: Option<u64>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let item_info = get_by_name!(ctx, item);
+
+    // Items are only checked by the tracking job, which needs a schedule to run
+    if ok_or_respond!(
+        ctx,
+        db::servers::get_schedule(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the schedule."
+    )
+    .is_none()
+    {
+        ctx.say(tr!(ctx, "Please set a schedule first.")).await?;
+        return Ok(());
+    }
+
+    if !ok_or_respond!(
+        ctx,
+        db::subscriptions::check_subscription(&ctx.data().pool, guild.id.0, item_info.id),
+        "An error occurred while checking the item."
+    ) {
+        ctx.say(tr!(ctx, "This item is not tracked.")).await?;
+        return Ok(());
+    }
+
+    ok_or_respond!(
+        ctx,
+        db::subscriptions::set_check_interval(&ctx.data().pool, guild.id.0, item_info.id, minutes),
+        "An error occurred while updating the check interval."
+    );
+
+    match minutes {
+        Some(minutes) => {
+            ctx.say(tr!(
+                ctx,
+                "{name} will be checked every {minutes} minutes.",
+                name = item_info.name,
+                minutes = minutes
+            ))
+            .await?
+        }
+        None => {
+            ctx.say(tr!(
+                ctx,
+                "{name} will be checked on the server schedule.",
+                name = item_info.name
+            ))
+            .await?
+        }
+    };
+
+    Ok(())
+}
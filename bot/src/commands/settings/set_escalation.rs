@@ -0,0 +1,86 @@
+use poise::serenity_prelude as serenity;
+
+use crate::{
+    commands::{
+        autocomplete::autocomplete_group,
+        checks::is_manager,
+        common::{get_group, get_guild, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    Context, Error,
+};
+
+/// Re-post updates nobody handled in time and ping a role. Leave empty to disable
+#[poise::command(
+    slash_command,
+    rename = "set_escalation",
+    check = "is_manager",
+    name_localized("de", "eskalation_festlegen"),
+    description_localized(
+        "de",
+        "Poste unerledigte Updates erneut und pinge eine Rolle. Leer lassen zum Deaktivieren"
+    )
+)]
+pub async fn set_escalation(
+    ctx: Context<'_>,
+    #[description = "Hours after which an unhandled update is escalated"]
+    #[description_localized("de", "Stunden, nach denen ein unerledigtes Update eskaliert wird")]
+    #[min = 1]
+    hours: Option<u64>,
+    #[description = "The role to ping when escalating"]
+    #[description_localized("de", "Die Rolle, die bei einer Eskalation gepingt wird")]
+    role: Option<serenity::Role>,
+    #[autocomplete = "autocomplete_group"]
+    #[description = "Only escalate updates of items in this group"]
+    #[description_localized("de", "Nur Updates von Einträgen dieser Gruppe eskalieren")]
+    group: Option<String>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let group_id = match &group {
+        Some(group) => Some(get_group!(ctx, guild.id.0, group)),
+        None => None,
+    };
+
+    let rule = match (hours, role) {
+        (Some(hours), Some(role)) => Some((hours, role.id.0)),
+        (None, None) => None,
+        _ => {
+            ctx.say(tr!(ctx, "Please provide both the hours and the role."))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    ok_or_respond!(
+        ctx,
+        db::events::set_escalation_rule(&ctx.data().pool, guild.id.0, rule, group_id),
+        "An error occurred while updating the escalation rule."
+    );
+
+    match rule {
+        Some((hours, role_id)) => {
+            let msg = match group {
+                Some(group) => tr!(
+                    ctx,
+                    "Updates of items in {group} not handled within {hours} hours will be escalated to <@&{role}>.",
+                    group = group,
+                    hours = hours,
+                    role = role_id
+                ),
+                None => tr!(
+                    ctx,
+                    "Updates not handled within {hours} hours will be escalated to <@&{role}>.",
+                    hours = hours,
+                    role = role_id
+                ),
+            };
+
+            ctx.say(msg).await?
+        }
+        None => ctx.say(tr!(ctx, "Escalation disabled.")).await?,
+    };
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use tracing::warn;
+
+use crate::{
+    commands::{
+        checks::is_manager,
+        common::{get_guild, ok_or_respond},
+    },
+    db::{self, ItemInfo},
+    hooks,
+    i18n::tr,
+    Context, Error,
+};
+
+/// Post a signed JSON event to an url whenever an item updates. Leave empty to remove
+#[poise::command(
+    slash_command,
+    rename = "set_hook",
+    check = "is_manager",
+    ephemeral,
+    name_localized("de", "hook_festlegen"),
+    description_localized(
+        "de",
+        "Sende bei jedem Update ein signiertes JSON-Event an eine URL. Leer lassen zum Entfernen"
+    )
+)]
+pub async fn set_hook(
+    ctx: Context<'_>,
+    #[description = "The url the events are posted to"]
+    #[description_localized("de", "Die URL, an die Events gesendet werden")]
+    url: Option<String>,
+    #[description = "The secret the events are signed with (HMAC-SHA256)"]
+    #[description_localized("de", "Das Geheimnis, mit dem Events signiert werden (HMAC-SHA256)")]
+    secret: Option<String>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let url = match url {
+        Some(url) => url,
+        None => {
+            ok_or_respond!(
+                ctx,
+                db::servers::set_hook(&ctx.data().pool, guild.id.0, None, None),
+                "An error occurred while removing the hook."
+            );
+
+            ctx.say(tr!(ctx, "Hook removed.")).await?;
+            return Ok(());
+        }
+    };
+
+    if !hooks::is_allowed_url(&url).await {
+        ctx.say(tr!(
+            ctx,
+            "Please provide an http or https url of a public host."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let secret = match secret {
+        Some(secret) if !secret.is_empty() => secret,
+        _ => {
+            ctx.say(tr!(ctx, "Please provide a secret to sign the events with."))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    ok_or_respond!(
+        ctx,
+        db::servers::set_hook(&ctx.data().pool, guild.id.0, Some(url), Some(secret)),
+        "An error occurred while updating the hook."
+    );
+
+    ctx.say(tr!(ctx, "Hook set. Use /test_hook to send a test event."))
+        .await?;
+
+    Ok(())
+}
+
+/// Send a test event to your hook
+#[poise::command(
+    slash_command,
+    rename = "test_hook",
+    check = "is_manager",
+    ephemeral,
+    name_localized("de", "hook_testen"),
+    description_localized("de", "Sende ein Test-Event an deinen Hook")
+)]
+pub async fn test_hook(ctx: Context<'_>) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let (url, secret) = match ok_or_respond!(
+        ctx,
+        db::servers::get_hook(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the hook."
+    ) {
+        Some(hook) => hook,
+        None => {
+            ctx.say(tr!(ctx, "Please set a hook first.")).await?;
+            return Ok(());
+        }
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    if !hooks::is_allowed_url(&url).await {
+        ctx.say(tr!(
+            ctx,
+            "The hook url is not allowed, please set it again with /set_hook."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let payload = hooks::to_payload(
+        guild.id.0,
+        hooks::EventKind::Test,
+        &hooks::UpdateEvent {
+            item_info: ItemInfo {
+                id: 0,
+                name: "Test item".to_string(),
+                last_updated: 1,
+                preview_url: None,
+                file_size: Some(0),
+            },
+            previous_update: 0,
+            note: Some("This is a test event".to_string()),
+        },
+    );
+
+    // The error could tell what is behind the url, so it is only logged
+    match hooks::deliver(&url, &secret, &payload).await {
+        Ok(_) => ctx.say(tr!(ctx, "Test event delivered.")).await?,
+        Err(e) => {
+            warn!(
+                "Test event to the hook of guild {} failed: {}",
+                guild.id.0, e
+            );
+            ctx.say(tr!(ctx, "Delivery failed.")).await?
+        }
+    };
+
+    Ok(())
+}
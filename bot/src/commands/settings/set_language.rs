@@ -0,0 +1,35 @@
+use crate::{
+    commands::{
+        checks::is_manager,
+        common::{get_guild, ok_or_respond},
+    },
+    i18n::{self, tr, Language},
+    Context, Error,
+};
+
+/// Set the language the bot answers in
+#[poise::command(
+    slash_command,
+    rename = "set_language",
+    check = "is_manager",
+    name_localized("de", "sprache_festlegen"),
+    description_localized("de", "Lege fest, in welcher Sprache der Bot antwortet")
+)]
+pub async fn set_language(
+    ctx: Context<'_>,
+    #[description = "The language of the replies and update messages"]
+    #[description_localized("de", "Die Sprache der Antworten und Update-Nachrichten")]
+    language: Language,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    ok_or_respond!(
+        ctx,
+        i18n::set_guild_language(&ctx.data().pool, guild.id.0, language),
+        "An error occurred while updating the language."
+    );
+
+    ctx.say(tr!(ctx, "The bot now answers in English.")).await?;
+
+    Ok(())
+}
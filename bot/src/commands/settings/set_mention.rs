@@ -0,0 +1,102 @@
+use poise::serenity_prelude as serenity;
+
+use crate::{
+    commands::{
+        autocomplete::{autocomplete_group, autocomplete_name},
+        checks::is_manager,
+        common::{get_by_name, get_group, get_guild, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    Context, Error,
+};
+
+/// Set who gets pinged when items update. Leave role and user empty to stop pinging
+#[poise::command(
+    slash_command,
+    rename = "set_mention",
+    check = "is_manager",
+    name_localized("de", "erwähnung_festlegen"),
+    description_localized(
+        "de",
+        "Lege fest, wer bei Updates gepingt wird. Ohne Rolle und Nutzer wird niemand gepingt"
+    )
+)]
+pub async fn set_mention(
+    ctx: Context<'_>,
+    #[description = "The role to mention"]
+    #[description_localized("de", "Die Rolle, die erwähnt wird")]
+    role: Option<serenity::Role>,
+    #[description = "The user to mention"]
+    #[description_localized("de", "Der Nutzer, der erwähnt wird")]
+    user: Option<serenity::User>,
+    #[autocomplete = "autocomplete_name"]
+    #[description = "Only mention for updates of this item"]
+    #[description_localized("de", "Nur bei Updates dieses Eintrags erwähnen")]
+    item: Option<String>,
+    #[autocomplete = "autocomplete_group"]
+    #[description = "Only mention for updates of items in this group"]
+    #[description_localized("de", "Nur bei Updates von Einträgen dieser Gruppe erwähnen")]
+    group: Option<String>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let role_id = role.map(|r| r.id.0);
+    let user_id = user.map(|u| u.id.0);
+
+    match (item, group) {
+        (Some(_), Some(_)) => {
+            ctx.say(tr!(ctx, "Please provide either an item or a group."))
+                .await?;
+            return Ok(());
+        }
+        (Some(item), None) => {
+            let item_info = get_by_name!(ctx, item);
+
+            if !ok_or_respond!(
+                ctx,
+                db::subscriptions::check_subscription(&ctx.data().pool, guild.id.0, item_info.id),
+                "An error occurred while checking the item."
+            ) {
+                ctx.say(tr!(ctx, "This item is not tracked.")).await?;
+                return Ok(());
+            }
+
+            ok_or_respond!(
+                ctx,
+                db::subscriptions::set_item_mention(
+                    &ctx.data().pool,
+                    guild.id.0,
+                    item_info.id,
+                    role_id,
+                    user_id
+                ),
+                "An error occurred while updating the mention."
+            );
+        }
+        (None, Some(group)) => {
+            let group_id = get_group!(ctx, guild.id.0, group);
+
+            ok_or_respond!(
+                ctx,
+                db::groups::set_group_mention(&ctx.data().pool, group_id, role_id, user_id),
+                "An error occurred while updating the mention."
+            );
+        }
+        (None, None) => {
+            ok_or_respond!(
+                ctx,
+                db::servers::set_mention(&ctx.data().pool, guild.id.0, role_id, user_id),
+                "An error occurred while updating the mention."
+            );
+        }
+    }
+
+    if role_id.is_none() && user_id.is_none() {
+        ctx.say(tr!(ctx, "Mention removed.")).await?;
+    } else {
+        ctx.say(tr!(ctx, "Mention set.")).await?;
+    }
+
+    Ok(())
+}
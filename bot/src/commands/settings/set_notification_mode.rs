@@ -0,0 +1,112 @@
+use crate::{
+    commands::{
+        checks::is_manager,
+        common::{get_guild, ok_or_respond},
+    },
+    db::{self, NotificationMode},
+    i18n::tr,
+    printer, Context, Error,
+};
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Weekday {
+    #[name_localized("de", "Montag")]
+    Monday,
+    #[name_localized("de", "Dienstag")]
+    Tuesday,
+    #[name_localized("de", "Mittwoch")]
+    Wednesday,
+    #[name_localized("de", "Donnerstag")]
+    Thursday,
+    #[name_localized("de", "Freitag")]
+    Friday,
+    #[name_localized("de", "Samstag")]
+    Saturday,
+    #[name_localized("de", "Sonntag")]
+    Sunday,
+}
+
+/// Choose between immediate notifications and a daily or weekly digest
+#[poise::command(
+    slash_command,
+    rename = "set_notification_mode",
+    check = "is_manager",
+    name_localized("de", "benachrichtigungsmodus_festlegen"),
+    description_localized(
+        "de",
+        "Wähle zwischen sofortigen Benachrichtigungen und einer täglichen oder wöchentlichen Übersicht"
+    )
+)]
+pub async fn set_notification_mode(
+    ctx: Context<'_>,
+    #[description = "When updates should be posted"]
+    #[description_localized("de", "Wann Updates gepostet werden")]
+    mode: NotificationMode,
+    #[description = "The hour the digest is posted at, in the timezone set with /set_timezone"]
+    #[description_localized(
+        "de",
+        "Die Stunde, zu der die Übersicht gepostet wird, in der Zeitzone von /set_timezone"
+    )]
+    #[max = 23]
+    hour: Option<u8>,
+    #[description = "The day the weekly digest is posted on"]
+    #[description_localized("de", "Der Tag, an dem die wöchentliche Übersicht gepostet wird")]
+    day: Option<Weekday>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let tz = ok_or_respond!(
+        ctx,
+        db::servers::get_timezone(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the timezone."
+    );
+
+    let hour = hour.unwrap_or(0);
+    let day = day.unwrap_or(Weekday::Monday);
+
+    let (msg, hour, day) = match mode {
+        NotificationMode::Immediate => {
+            (tr!(ctx, "Updates will be posted immediately."), None, None)
+        }
+        NotificationMode::Daily => (
+            tr!(
+                ctx,
+                "Updates will be posted every day at {hour}:00 {timezone}.",
+                hour = format!("{:02}", hour),
+                timezone = tz.name()
+            ),
+            Some(hour),
+            None,
+        ),
+        NotificationMode::Weekly => (
+            tr!(
+                ctx,
+                "Updates will be posted every {day} at {hour}:00 {timezone}.",
+                day = tr!(ctx, day.name()),
+                hour = format!("{:02}", hour),
+                timezone = tz.name()
+            ),
+            Some(hour),
+            Some(day as u8),
+        ),
+    };
+
+    ok_or_respond!(
+        ctx,
+        db::servers::set_notification_mode(&ctx.data().pool, guild.id.0, mode, hour, day),
+        "An error occurred while updating the notification mode."
+    );
+
+    if mode == NotificationMode::Immediate {
+        // Don't hold back anything that was collected for a digest
+        ok_or_respond!(
+            ctx,
+            printer::send_digest(ctx.data().scheduler.clone(), guild.id.0).await,
+            "An error occurred while posting the collected updates."
+        );
+    }
+
+    ctx.say(msg).await?;
+
+    Ok(())
+}
@@ -1,27 +1,49 @@
 use crate::{
-    commands::common::{get_guild, ok_or_respond},
-    db, Context, Error,
+    commands::{
+        checks::is_manager,
+        common::{get_guild, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    Context, Error,
 };
 
 /// Set how often the bot should look for updates
-#[poise::command(slash_command, rename = "set_schedule")]
+#[poise::command(
+    slash_command,
+    rename = "set_schedule",
+    check = "is_manager",
+    name_localized("de", "zeitplan_festlegen"),
+    description_localized("de", "Lege fest, wie oft der Bot nach Updates sucht")
+)]
 pub async fn set_schedule(
     ctx: Context<'_>,
-    #[description = "Updates will bec checked every x hours"] interval: u64,
+    #[description = "Updates will bec checked every x hours"]
+    #[description_localized("de", "Updates werden alle x Stunden gesucht")]
+    interval: u64,
 ) -> Result<(), Error> {
     let guild = get_guild!(ctx);
 
     let state = ctx.data().clone();
-    match db::servers::get_update_channel(&state.pool, guild.id.0) {
-        Ok(channel) => {
-            if channel.is_none() {
-                ctx.say("Please set an update channel first.").await?;
+    match db::servers::get_update_channel(&state.pool, guild.id.0)
+        .and_then(|c| Ok((c, db::servers::get_webhook(&state.pool, guild.id.0)?)))
+    {
+        Ok((channel, webhook)) => {
+            if channel.is_none() && webhook.is_none() {
+                ctx.say(tr!(
+                    ctx,
+                    "Please set an update channel first, or run /setup."
+                ))
+                .await?;
                 return Ok(());
             }
         }
         Err(_) => {
-            ctx.say("An error occurred while fetching the update channel.")
-                .await?;
+            ctx.say(tr!(
+                ctx,
+                "An error occurred while fetching the update channel."
+            ))
+            .await?;
             return Ok(());
         }
     }
@@ -34,7 +56,7 @@ pub async fn set_schedule(
 
     state.scheduler.start_schedule(guild.id.0).await?;
 
-    ctx.say("Schedule set.").await?;
+    ctx.say(tr!(ctx, "Schedule set.")).await?;
 
     Ok(())
 }
@@ -0,0 +1,131 @@
+use crate::{
+    commands::{
+        checks::is_manager,
+        common::{get_guild, ok_or_respond},
+    },
+    db::{self, ItemInfo},
+    i18n::{self, tr},
+    printer::item_to_embed,
+    template::{self, TemplateKind},
+    Context, Error,
+};
+
+/// Customize the update messages. Leave the template empty to use the default
+#[poise::command(
+    slash_command,
+    rename = "set_template",
+    check = "is_manager",
+    name_localized("de", "vorlage_festlegen"),
+    description_localized(
+        "de",
+        "Passe die Update-Nachrichten an. Ohne Vorlage wird die Standardvorlage verwendet"
+    )
+)]
+pub async fn set_template(
+    ctx: Context<'_>,
+    #[description = "Which part of the update message to change"]
+    #[description_localized("de", "Welcher Teil der Update-Nachricht geändert wird")]
+    kind: TemplateKind,
+    #[description = "The template. Placeholders: {name} {id} {url} {note} {updated_at} {size} {count}"]
+    #[description_localized(
+        "de",
+        "Die Vorlage. Platzhalter: {name} {id} {url} {note} {updated_at} {size} {count}"
+    )]
+    template: Option<String>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let template = template.filter(|t| !t.trim().is_empty());
+
+    if let Some(template) = &template {
+        if let Err(e) = template::validate(template, kind.placeholders()) {
+            ctx.say(tr!(ctx, "Invalid template: {error}", error = e))
+                .await?;
+            return Ok(());
+        }
+    }
+
+    ok_or_respond!(
+        ctx,
+        db::servers::set_template(&ctx.data().pool, guild.id.0, kind, template.clone()),
+        "An error occurred while updating the template."
+    );
+
+    match template {
+        Some(_) => {
+            ctx.say(tr!(
+                ctx,
+                "Template set. Use /preview_template to see how it looks."
+            ))
+            .await?
+        }
+        None => ctx.say(tr!(ctx, "Template reset to the default.")).await?,
+    };
+
+    Ok(())
+}
+
+/// Show how an update message looks with your templates
+#[poise::command(
+    slash_command,
+    rename = "preview_template",
+    ephemeral,
+    name_localized("de", "vorlage_vorschau"),
+    description_localized("de", "Zeige, wie eine Update-Nachricht mit deinen Vorlagen aussieht")
+)]
+pub async fn preview_template(ctx: Context<'_>) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let templates = ok_or_respond!(
+        ctx,
+        db::servers::get_templates(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the templates."
+    );
+
+    let subscriptions = ok_or_respond!(
+        ctx,
+        db::subscriptions::get_all_subscriptions_of_guild(&ctx.data().pool, guild.id.0),
+        "An error occurred while fetching the subscriptions."
+    );
+
+    // Use one of the tracked items if there is one, so the preview looks like the real thing
+    let (item_info, note) = match subscriptions.into_iter().next() {
+        Some((_, item_info, note)) => (item_info, note),
+        None => (
+            ItemInfo {
+                id: 450814997,
+                name: "Sample item".to_string(),
+                last_updated: 1690000000,
+                preview_url: None,
+                file_size: Some(1048576),
+            },
+            Some("This is a note".to_string()),
+        ),
+    };
+
+    let language = i18n::guild_language(&ctx.data().pool, Some(guild.id.0));
+    let embed = item_to_embed(
+        &item_info,
+        &note,
+        templates.item.as_deref(),
+        1,
+        &[],
+        None,
+        language,
+    );
+
+    ctx.send(|b| {
+        b.content(templates.header(
+            i18n::translate(language, "The following items were updated:"),
+            1,
+        ));
+        b.embed(|e| {
+            *e = embed;
+            e
+        });
+        b
+    })
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use chrono_tz::Tz;
+
+use crate::{
+    commands::{
+        autocomplete::autocomplete_timezone,
+        checks::is_manager,
+        common::{get_guild, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    Context, Error,
+};
+
+/// Set the timezone dates and digest times are read in
+#[poise::command(
+    slash_command,
+    rename = "set_timezone",
+    check = "is_manager",
+    name_localized("de", "zeitzone_festlegen"),
+    description_localized(
+        "de",
+        "Lege die Zeitzone fest, in der Daten und Übersichtszeiten gelesen werden"
+    )
+)]
+pub async fn set_timezone(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_timezone"]
+    #[description = "The name of the timezone, e.g. Europe/Berlin. Leave empty to use UTC"]
+    #[description_localized(
+        "de",
+        "Der Name der Zeitzone, z.B. Europe/Berlin. Leer lassen für UTC"
+    )]
+    timezone: Option<String>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let tz = match timezone.map(|t| t.trim().parse::<Tz>()) {
+        Some(Ok(tz)) => Some(tz),
+        Some(Err(_)) => {
+            ctx.say(tr!(
+                ctx,
+                "Unknown timezone. Use a name like Europe/Berlin or America/New_York."
+            ))
+            .await?;
+            return Ok(());
+        }
+        None => None,
+    };
+
+    ok_or_respond!(
+        ctx,
+        db::servers::set_timezone(&ctx.data().pool, guild.id.0, tz),
+        "An error occurred while updating the timezone."
+    );
+
+    ctx.say(tr!(
+        ctx,
+        "Dates are now read in {timezone}.",
+        timezone = tz.unwrap_or(Tz::UTC).name()
+    ))
+    .await?;
+
+    Ok(())
+}
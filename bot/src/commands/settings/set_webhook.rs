@@ -0,0 +1,91 @@
+use poise::serenity_prelude::Webhook;
+
+use crate::{
+    commands::{
+        checks::is_manager,
+        common::{get_channel, get_guild, get_guild_channel, ok_or_respond},
+    },
+    db,
+    i18n::tr,
+    Context, Error,
+};
+
+/// Post updates through a webhook. Without an url, one is created in the update channel
+#[poise::command(
+    slash_command,
+    rename = "set_webhook",
+    check = "is_manager",
+    name_localized("de", "webhook_festlegen"),
+    description_localized(
+        "de",
+        "Poste Updates über einen Webhook. Ohne URL wird einer im Update-Kanal erstellt"
+    )
+)]
+pub async fn set_webhook(
+    ctx: Context<'_>,
+    #[description = "Url of an existing webhook, may be in another server"]
+    #[description_localized("de", "URL eines bestehenden Webhooks, auch auf einem anderen Server")]
+    url: Option<String>,
+    #[description = "The name the updates are posted with"]
+    #[description_localized("de", "Der Name, mit dem Updates gepostet werden")]
+    name: Option<String>,
+    #[description = "Url of the avatar the updates are posted with"]
+    #[description_localized("de", "URL des Avatars, mit dem Updates gepostet werden")]
+    avatar_url: Option<String>,
+    #[description = "Stop using a webhook and post as the bot again"]
+    #[description_localized("de", "Keinen Webhook mehr verwenden und wieder als Bot posten")]
+    disable: Option<bool>,
+) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    if disable.unwrap_or(false) {
+        ok_or_respond!(
+            ctx,
+            db::servers::set_webhook(&ctx.data().pool, guild.id.0, None, None, None),
+            "An error occurred while removing the webhook."
+        );
+
+        ctx.say(tr!(
+            ctx,
+            "Webhook removed. Updates will be posted by the bot."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let url = match url {
+        Some(url) => {
+            ok_or_respond!(
+                ctx,
+                Webhook::from_url(ctx, &url).await,
+                "Please provide a valid webhook url."
+            );
+
+            url
+        }
+        None => {
+            let item_channel = get_channel!(ctx, guild.id.0);
+
+            let g = get_guild_channel!(ctx, guild, item_channel);
+
+            let webhook = ok_or_respond!(
+                ctx,
+                g.create_webhook(ctx, name.as_deref().unwrap_or("Workshop Updates"))
+                    .await,
+                "Could not create a webhook. Please make sure I have the Manage Webhooks permission."
+            );
+
+            ok_or_respond!(ctx, webhook.url(), "Could not create a webhook.")
+        }
+    };
+
+    ok_or_respond!(
+        ctx,
+        db::servers::set_webhook(&ctx.data().pool, guild.id.0, Some(url), name, avatar_url),
+        "An error occurred while updating the webhook."
+    );
+
+    ctx.say(tr!(ctx, "Webhook set.")).await?;
+
+    Ok(())
+}
@@ -0,0 +1,522 @@
+use std::{sync::Arc, time::Duration};
+
+use poise::serenity_prelude::{
+    ButtonStyle, ChannelType, CreateComponents, Guild, GuildChannel, InteractionResponseType,
+    Message, MessageComponentInteraction,
+};
+
+use crate::{
+    commands::{
+        checks::is_manager,
+        common::{get_guild, ok_or_respond},
+    },
+    db::{self, Mention},
+    i18n::tr,
+    printer, Context, Error,
+};
+
+/// How long every step of the setup waits for an answer
+const TIMEOUT: Duration = Duration::from_secs(60 * 10);
+
+/// The intervals offered by the schedule picker, in hours
+const SCHEDULES: [u64; 6] = [1, 2, 4, 6, 12, 24];
+
+/// Select menus can't have more options than this, longer lists are paged
+const MAX_OPTIONS: usize = 25;
+
+/// Set up the update channel, the role to ping and the schedule step by step
+#[poise::command(
+    slash_command,
+    rename = "setup",
+    check = "is_manager",
+    ephemeral,
+    name_localized("de", "einrichten"),
+    description_localized(
+        "de",
+        "Richte Update-Kanal, zu pingende Rolle und Zeitplan Schritt für Schritt ein"
+    )
+)]
+pub async fn setup(ctx: Context<'_>) -> Result<(), Error> {
+    let guild = get_guild!(ctx);
+
+    let channels = text_channels(&guild);
+
+    if channels.is_empty() {
+        ctx.say(tr!(
+            ctx,
+            "This server has no text channels to post updates in."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let reply = ctx
+        .send(|b| {
+            b.content(tr!(
+                ctx,
+                "**Step 1 of 3:** Which channel should updates be posted in?"
+            ));
+            b.components(|c| {
+                *c = channel_components(ctx, &channels, 0);
+                c
+            });
+            b
+        })
+        .await?;
+
+    let message = reply.message().await?;
+
+    // Step 1: a channel the bot can post in
+    let mut page = 0;
+    let (mc, channel) = loop {
+        let mc = match next_choice(ctx, &message).await? {
+            Some(mc) => mc,
+            None => return Ok(()),
+        };
+
+        let mut content = tr!(
+            ctx,
+            "**Step 1 of 3:** Which channel should updates be posted in?"
+        );
+
+        if let Some(p) = turn_page(&mc, page, channels.len()) {
+            page = p;
+        } else if let Some(channel) = mc
+            .data
+            .values
+            .first()
+            .and_then(|v| channels.iter().find(|c| &c.id.0.to_string() == v))
+        {
+            let missing = missing_permissions(ctx, &guild, channel).await?;
+
+            if missing.is_empty() {
+                break (mc, channel.clone());
+            }
+
+            content = tr!(
+                ctx,
+                ":warning: I can't post in {channel}, I am missing these permissions: {permissions}\n**Step 1 of 3:** Which channel should updates be posted in?",
+                channel = format!("<#{}>", channel.id.0),
+                permissions = missing.join(", ")
+            );
+        }
+
+        // Anything else, like a channel deleted in the meantime, just asks again
+        update(ctx, &mc, content, channel_components(ctx, &channels, page)).await?;
+    };
+
+    // Step 2: who to ping
+    let roles = ping_roles(&guild);
+    let prompt = tr!(
+        ctx,
+        "**Step 2 of 3:** Which role should be pinged when items update?"
+    );
+
+    update(ctx, &mc, prompt.clone(), role_components(ctx, &roles, 0)).await?;
+
+    let mut page = 0;
+    let (mc, role) = loop {
+        let mc = match next_choice(ctx, &message).await? {
+            Some(mc) => mc,
+            None => return Ok(()),
+        };
+
+        if mc.data.custom_id == "setup:no_role" {
+            break (mc, None);
+        }
+
+        if let Some(p) = turn_page(&mc, page, roles.len()) {
+            page = p;
+        } else if let Some(role) = mc
+            .data
+            .values
+            .first()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|r| roles.iter().any(|(id, _)| id == r))
+        {
+            break (mc, Some(role));
+        }
+
+        update(ctx, &mc, prompt.clone(), role_components(ctx, &roles, page)).await?;
+    };
+
+    // Step 3: how often to check
+    let prompt = tr!(ctx, "**Step 3 of 3:** How often should I look for updates?");
+
+    update(ctx, &mc, prompt.clone(), schedule_components(ctx)).await?;
+
+    let (mc, schedule) = loop {
+        let mc = match next_choice(ctx, &message).await? {
+            Some(mc) => mc,
+            None => return Ok(()),
+        };
+
+        if let Some(s) = mc
+            .data
+            .values
+            .first()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|s| SCHEDULES.contains(s))
+        {
+            break (mc, s);
+        }
+
+        update(ctx, &mc, prompt.clone(), schedule_components(ctx)).await?;
+    };
+
+    update(
+        ctx,
+        &mc,
+        tr!(ctx, "Processing... Please wait."),
+        CreateComponents::default(),
+    )
+    .await?;
+
+    // Only save the settings once we know the channel works
+    if let Err(e) = channel
+        .send_message(ctx, |m| {
+            m.content(tr!(
+                ctx,
+                ":white_check_mark: This is a test message. Updates of the tracked items will be posted here."
+            ))
+        })
+        .await
+    {
+        reply
+            .edit(ctx, |b| {
+                b.content(tr!(
+                    ctx,
+                    ":warning: I could not post in {channel}: {error}",
+                    channel = format!("<#{}>", channel.id.0),
+                    error = e
+                ))
+            })
+            .await?;
+        return Ok(());
+    }
+
+    let pool = &ctx.data().pool;
+
+    if !ok_or_respond!(
+        ctx,
+        db::servers::check_still_in_guild(pool, guild.id.0),
+        "An error occurred while checking if the bot is still in the guild."
+    ) {
+        ok_or_respond!(
+            ctx,
+            db::servers::add_server(pool, &guild),
+            "An error occurred while adding the server to the database."
+        );
+    }
+
+    ok_or_respond!(
+        ctx,
+        db::servers::set_update_channel(pool, guild.id.0, channel.id.0),
+        "An error occurred while updating the channel."
+    );
+
+    // A user that is pinged already keeps being pinged
+    let user = ok_or_respond!(
+        ctx,
+        db::servers::get_mentions(pool, guild.id.0),
+        "An error occurred while updating the mention."
+    )
+    .into_iter()
+    .find_map(|m| match m {
+        Mention::User(id) => Some(id),
+        Mention::Role(_) => None,
+    });
+
+    ok_or_respond!(
+        ctx,
+        db::servers::set_mention(pool, guild.id.0, role, user),
+        "An error occurred while updating the mention."
+    );
+
+    ok_or_respond!(
+        ctx,
+        db::servers::set_schedule(pool, guild.id.0, schedule),
+        "An error occurred while updating the schedule."
+    );
+
+    ok_or_respond!(
+        ctx,
+        ctx.data().scheduler.start_schedule(guild.id.0).await,
+        "An error occurred while restarting the tracking job."
+    );
+
+    reply
+        .edit(ctx, |b| {
+            b.content(tr!(
+                ctx,
+                "All set! Updates will be posted in {channel}, I will look for them {schedule}. Add items with /add or /add_collection.",
+                channel = format!("<#{}>", channel.id.0),
+                schedule = schedule_label(ctx, schedule)
+            ))
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Waits for the next selection of the author.
+/// Returns none if they cancelled or didn't answer in time, they have been told then.
+async fn next_choice(
+    ctx: Context<'_>,
+    message: &Message,
+) -> Result<Option<Arc<MessageComponentInteraction>>, Error> {
+    let mc = match message
+        .await_component_interaction(ctx)
+        .author_id(ctx.author().id)
+        .timeout(TIMEOUT)
+        .await
+    {
+        Some(mc) => mc,
+        None => {
+            ctx.say(tr!(
+                ctx,
+                ":warning: You didn't interact in time - please run the command again."
+            ))
+            .await?;
+            return Ok(None);
+        }
+    };
+
+    if mc.data.custom_id == "setup:cancel" {
+        update(
+            ctx,
+            &mc,
+            tr!(ctx, "Cancelled."),
+            CreateComponents::default(),
+        )
+        .await?;
+        return Ok(None);
+    }
+
+    Ok(Some(mc))
+}
+
+async fn update(
+    ctx: Context<'_>,
+    mc: &MessageComponentInteraction,
+    content: String,
+    components: CreateComponents,
+) -> Result<(), Error> {
+    mc.create_interaction_response(ctx, |r| {
+        r.kind(InteractionResponseType::UpdateMessage);
+        r.interaction_response_data(|d| {
+            d.content(content);
+            d.set_components(components);
+            d
+        });
+        r
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// The channels updates can be posted in, in the order of the channel list
+fn text_channels(guild: &Guild) -> Vec<GuildChannel> {
+    let mut channels: Vec<GuildChannel> = guild
+        .channels
+        .values()
+        .filter_map(|c| c.clone().guild())
+        .filter(|c| matches!(c.kind, ChannelType::Text | ChannelType::News))
+        .collect();
+
+    channels.sort_by_key(|c| c.position);
+    channels
+}
+
+/// The roles that can be pinged, highest first, leaving out @everyone and the roles of integrations
+fn ping_roles(guild: &Guild) -> Vec<(u64, String)> {
+    let mut roles: Vec<_> = guild
+        .roles
+        .values()
+        .filter(|r| r.id.0 != guild.id.0 && !r.managed)
+        .collect();
+    roles.sort_by_key(|r| std::cmp::Reverse(r.position));
+
+    roles
+        .into_iter()
+        .map(|r| (r.id.0, format!("@{}", r.name)))
+        .collect()
+}
+
+/// Returns the new page if the author pressed one of the page buttons
+fn turn_page(mc: &MessageComponentInteraction, page: usize, options: usize) -> Option<usize> {
+    let pages = options.div_ceil(MAX_OPTIONS);
+
+    match mc.data.custom_id.as_str() {
+        "setup:prev" => Some(page.saturating_sub(1)),
+        "setup:next" => Some((page + 1).min(pages.saturating_sub(1))),
+        _ => None,
+    }
+}
+
+/// The permissions the bot needs to post updates that it doesn't have in the channel
+async fn missing_permissions(
+    ctx: Context<'_>,
+    guild: &Guild,
+    channel: &GuildChannel,
+) -> Result<Vec<String>, Error> {
+    let bot = guild
+        .member(ctx, ctx.serenity_context().cache.current_user_id())
+        .await?;
+    let permissions = guild.user_permissions_in(channel, &bot)?;
+
+    Ok([
+        (permissions.view_channel(), "View Channel"),
+        (permissions.send_messages(), "Send Messages"),
+        (permissions.embed_links(), "Embed Links"),
+    ]
+    .into_iter()
+    .filter(|(has, _)| !has)
+    .map(|(_, name)| tr!(ctx, name))
+    .collect())
+}
+
+fn channel_components(
+    ctx: Context<'_>,
+    channels: &[GuildChannel],
+    page: usize,
+) -> CreateComponents {
+    let options: Vec<(u64, String)> = channels
+        .iter()
+        .map(|c| (c.id.0, format!("#{}", c.name)))
+        .collect();
+
+    let mut c = select_components("setup:channel", tr!(ctx, "Update channel"), &options, page);
+    buttons(ctx, &mut c, page, options.len(), None);
+    c
+}
+
+fn role_components(ctx: Context<'_>, roles: &[(u64, String)], page: usize) -> CreateComponents {
+    let mut c = CreateComponents::default();
+
+    if !roles.is_empty() {
+        c = select_components("setup:role", tr!(ctx, "Role to ping"), roles, page);
+    }
+
+    buttons(
+        ctx,
+        &mut c,
+        page,
+        roles.len(),
+        Some(("setup:no_role", tr!(ctx, "Don't ping a role"))),
+    );
+    c
+}
+
+/// A select menu with one page of the options, select menus can't have more than 25
+fn select_components(
+    custom_id: &str,
+    placeholder: String,
+    options: &[(u64, String)],
+    page: usize,
+) -> CreateComponents {
+    let mut c = CreateComponents::default();
+
+    c.create_action_row(|r| {
+        r.create_select_menu(|m| {
+            m.custom_id(custom_id);
+            m.placeholder(placeholder);
+            m.options(|o| {
+                for (value, label) in options.iter().skip(page * MAX_OPTIONS).take(MAX_OPTIONS) {
+                    o.create_option(|opt| {
+                        opt.label(printer::truncate(label, 100));
+                        opt.value(value);
+                        opt
+                    });
+                }
+                o
+            });
+            m
+        });
+        r
+    });
+
+    c
+}
+
+/// Adds the page buttons if the options don't fit on one page, an optional extra button and the cancel button
+fn buttons(
+    ctx: Context<'_>,
+    c: &mut CreateComponents,
+    page: usize,
+    options: usize,
+    extra: Option<(&str, String)>,
+) {
+    let pages = options.div_ceil(MAX_OPTIONS);
+
+    c.create_action_row(|r| {
+        if pages > 1 {
+            r.create_button(|b| {
+                b.style(ButtonStyle::Secondary);
+                b.label(tr!(ctx, "Prev"));
+                b.custom_id("setup:prev");
+                b.disabled(page == 0);
+                b
+            });
+            r.create_button(|b| {
+                b.style(ButtonStyle::Secondary);
+                b.label(tr!(ctx, "Next"));
+                b.custom_id("setup:next");
+                b.disabled(page + 1 >= pages);
+                b
+            });
+        }
+        if let Some((custom_id, label)) = extra {
+            r.create_button(|b| {
+                b.style(ButtonStyle::Secondary);
+                b.label(label);
+                b.custom_id(custom_id);
+                b
+            });
+        }
+        r.create_button(|b| {
+            b.style(ButtonStyle::Danger);
+            b.label(tr!(ctx, "Cancel"));
+            b.custom_id("setup:cancel");
+            b
+        });
+        r
+    });
+}
+
+fn schedule_components(ctx: Context<'_>) -> CreateComponents {
+    let mut c = CreateComponents::default();
+
+    c.create_action_row(|r| {
+        r.create_select_menu(|m| {
+            m.custom_id("setup:schedule");
+            m.placeholder(tr!(ctx, "Schedule"));
+            m.options(|o| {
+                for hours in SCHEDULES {
+                    o.create_option(|opt| {
+                        opt.label(match hours {
+                            1 => tr!(ctx, "Every hour"),
+                            h => tr!(ctx, "Every {hours} hours", hours = h),
+                        });
+                        opt.value(hours);
+                        opt
+                    });
+                }
+                o
+            });
+            m
+        });
+        r
+    });
+
+    buttons(ctx, &mut c, 0, SCHEDULES.len(), None);
+    c
+}
+
+fn schedule_label(ctx: Context<'_>, hours: u64) -> String {
+    match hours {
+        1 => tr!(ctx, "every hour"),
+        h => tr!(ctx, "every {hours} hours", hours = h),
+    }
+}
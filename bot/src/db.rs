@@ -1,10 +1,19 @@
+use std::fmt;
+
 use mysql::{Opts, Pool};
 
 use crate::Error;
 
+pub mod audit;
+pub mod digests;
+pub mod events;
+pub mod groups;
 pub mod items;
+pub mod migrations;
+pub mod notes;
 pub mod servers;
 pub mod subscriptions;
+pub mod watches;
 
 #[derive(Debug, Clone)]
 pub struct ItemInfo {
@@ -12,6 +21,90 @@ pub struct ItemInfo {
     pub name: String,
     pub last_updated: u64,
     pub preview_url: Option<String>,
+    pub file_size: Option<u64>,
+}
+
+/// Who added an item to a guild, and how
+#[derive(Debug, Clone, Default)]
+pub struct Provenance {
+    pub added_at: Option<u64>,
+    pub added_by: Option<u64>,
+    /// The collection the item was added with
+    pub collection: Option<u64>,
+    /// Filled in by the printer, embed footers can't render mentions
+    pub added_by_name: Option<String>,
+}
+
+/// Someone to ping when an update notification is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mention {
+    Role(u64),
+    User(u64),
+}
+
+impl Mention {
+    /// Builds the mentions stored in a `MentionRole`/`MentionUser` column pair
+    pub fn from_columns(role: Option<u64>, user: Option<u64>) -> Vec<Mention> {
+        role.map(Mention::Role)
+            .into_iter()
+            .chain(user.map(Mention::User))
+            .collect()
+    }
+}
+
+impl fmt::Display for Mention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mention::Role(id) => write!(f, "<@&{}>", id),
+            Mention::User(id) => write!(f, "<@{}>", id),
+        }
+    }
+}
+
+/// How a guild wants to be told about updates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum NotificationMode {
+    #[name = "Immediately"]
+    #[name_localized("de", "Sofort")]
+    Immediate,
+    #[name = "Daily digest"]
+    #[name_localized("de", "Tägliche Übersicht")]
+    Daily,
+    #[name = "Weekly digest"]
+    #[name_localized("de", "Wöchentliche Übersicht")]
+    Weekly,
+}
+
+impl NotificationMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationMode::Immediate => "immediate",
+            NotificationMode::Daily => "daily",
+            NotificationMode::Weekly => "weekly",
+        }
+    }
+
+    pub fn from_db(s: &str) -> Result<Self, Error> {
+        match s {
+            "immediate" => Ok(NotificationMode::Immediate),
+            "daily" => Ok(NotificationMode::Daily),
+            "weekly" => Ok(NotificationMode::Weekly),
+            _ => Err(format!("Unknown notification mode: {}", s).into()),
+        }
+    }
+}
+
+/// What the members of a role may do with the bot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum PermissionLevel {
+    /// Settings and destructive commands
+    #[name = "Manager"]
+    #[name_localized("de", "Verwalter")]
+    Manager,
+    /// Adding items and editing notes
+    #[name = "Contributor"]
+    #[name_localized("de", "Mitwirkender")]
+    Contributor,
 }
 
 pub fn get_pool(url: &str) -> Result<Pool, Error> {
@@ -19,3 +112,48 @@ pub fn get_pool(url: &str) -> Result<Pool, Error> {
 
     Ok(pool)
 }
+
+/// The tests using it are ignored by default, run them with `cargo test -- --ignored`.
+/// Returns none if the variable is not set, the tests using it are skipped then.
+#[cfg(test)]
+pub fn test_pool() -> (Pool, u64) {
+    use mysql::prelude::Queryable;
+
+    let url = std::env::var("TEST_MYSQL_URL").expect("TEST_MYSQL_URL is not set");
+    let pool = get_pool(&url).expect("Failed to connect to the test database");
+
+    let mut conn = pool.get_conn().unwrap();
+    for statement in include_str!("../../db/init.sql").split(';') {
+        if !statement.trim().is_empty() {
+            conn.query_drop(statement).unwrap();
+        }
+    }
+    migrations::migrate(&pool).unwrap();
+
+    // Every test gets its own server, so they can run at the same time
+    let guild_id = rand_guild_id();
+    conn.query_drop(format!(
+        "INSERT INTO Servers (ServerId) VALUES ({});",
+        guild_id
+    ))
+    .unwrap();
+
+    (pool, guild_id)
+}
+
+#[cfg(test)]
+fn rand_guild_id() -> u64 {
+    use std::{
+        sync::atomic::{AtomicU64, Ordering},
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64;
+
+    (nanos >> 8) + COUNTER.fetch_add(1, Ordering::Relaxed)
+}
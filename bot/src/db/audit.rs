@@ -0,0 +1,101 @@
+use mysql::{params, prelude::Queryable, Pool};
+
+use crate::Error;
+
+/// The size of the OldValue and NewValue columns
+pub const MAX_VALUE_LENGTH: usize = 500;
+
+/// A single value changed by a command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditChange {
+    pub item_id: Option<u64>,
+    pub field: &'static str,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// A recorded change, with the name of the item if it still exists
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub user_id: u64,
+    pub command: String,
+    pub item: Option<(u64, Option<String>)>,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: u64,
+}
+
+pub fn add_entries(
+    pool: &Pool,
+    guild_id: u64,
+    user_id: u64,
+    command: &str,
+    changes: &[AuditChange],
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_batch(
+        r"INSERT INTO AuditLog (ServerId, UserId, Command, ItemId, Field, OldValue, NewValue, CreatedAt) VALUES (:guild_id, :user_id, :command, :item_id, :field, :old_value, :new_value, UNIX_TIMESTAMP());",
+        changes.iter().map(|c| {
+            params! {
+                "guild_id" => guild_id,
+                "user_id" => user_id,
+                "command" => command,
+                "item_id" => c.item_id,
+                "field" => c.field,
+                "old_value" => &c.old_value,
+                "new_value" => &c.new_value,
+            }
+        }),
+    )?;
+    Ok(())
+}
+
+/// Returns the latest changes of a guild, newest first, optionally only those of one item or user
+#[allow(clippy::type_complexity)]
+pub fn get_entries(
+    pool: &Pool,
+    guild_id: u64,
+    item_id: Option<u64>,
+    user_id: Option<u64>,
+    limit: u64,
+) -> Result<Vec<AuditEntry>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(
+        u64,
+        String,
+        Option<u64>,
+        Option<String>,
+        String,
+        Option<String>,
+        Option<String>,
+        u64,
+    )> = conn.exec(
+        r"SELECT AuditLog.UserId, AuditLog.Command, AuditLog.ItemId, Items.ItemName, AuditLog.Field, AuditLog.OldValue, AuditLog.NewValue, AuditLog.CreatedAt FROM AuditLog LEFT JOIN Items ON AuditLog.ItemId = Items.ItemId WHERE AuditLog.ServerId = :guild_id AND (:item_id IS NULL OR AuditLog.ItemId = :item_id) AND (:user_id IS NULL OR AuditLog.UserId = :user_id) ORDER BY AuditLog.AuditId DESC LIMIT :limit",
+        params! {
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+            "user_id" => user_id,
+            "limit" => limit,
+        },
+    )?;
+
+    Ok(res
+        .into_iter()
+        .map(
+            |(user_id, command, item_id, name, field, old_value, new_value, created_at)| {
+                AuditEntry {
+                    user_id,
+                    command,
+                    item: item_id.map(|id| (id, name)),
+                    field,
+                    old_value,
+                    new_value,
+                    created_at,
+                }
+            },
+        )
+        .collect())
+}
@@ -0,0 +1,67 @@
+use mysql::{params, prelude::Queryable, Pool};
+
+use crate::Error;
+
+use super::ItemInfo;
+
+pub fn add_pending_updates(pool: &Pool, guild_id: u64, item_ids: &[u64]) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_batch(
+        r"INSERT IGNORE INTO PendingDigests (ServerId, ItemId, DetectedAt) VALUES (:guild_id, :item_id, UNIX_TIMESTAMP());",
+        item_ids.iter().map(|item_id| {
+            params! {
+                "guild_id" => guild_id,
+                "item_id" => item_id,
+            }
+        }),
+    )?;
+    Ok(())
+}
+
+/// Returns every item with pending updates, how often it was updated and its note
+#[allow(clippy::type_complexity)]
+pub fn get_pending_updates(
+    pool: &Pool,
+    guild_id: u64,
+) -> Result<Vec<(ItemInfo, u64, Option<String>)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, String, u64, Option<String>, Option<u64>, u64, Option<String>)> = conn.query(format!(
+        "SELECT Items.ItemId, Items.ItemName, Items.LastUpdate, Items.PreviewUrl, Items.FileSize, COUNT(*), Subscriptions.Note FROM PendingDigests INNER JOIN Items ON PendingDigests.ItemId = Items.ItemId INNER JOIN Subscriptions ON PendingDigests.ServerId = Subscriptions.ServerId AND PendingDigests.ItemId = Subscriptions.ItemId WHERE PendingDigests.ServerId = {} AND Subscriptions.RemovedAt IS NULL GROUP BY Items.ItemId, Subscriptions.Note ORDER BY Items.ItemName",
+        guild_id
+    ))?;
+
+    Ok(res
+        .into_iter()
+        .map(
+            |(id, name, last_updated, preview_url, file_size, count, note)| {
+                (
+                    ItemInfo {
+                        id,
+                        name,
+                        last_updated,
+                        preview_url,
+                        file_size,
+                    },
+                    count,
+                    note,
+                )
+            },
+        )
+        .collect())
+}
+
+/// Removes the pending updates detected up to `until`, so updates found meanwhile are kept
+pub fn clear_pending_updates(pool: &Pool, guild_id: u64, until: u64) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"DELETE FROM PendingDigests WHERE ServerId = :guild_id AND DetectedAt <= :until;",
+        params! {
+            "guild_id" => guild_id,
+            "until" => until,
+        },
+    )?;
+    Ok(())
+}
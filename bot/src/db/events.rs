@@ -0,0 +1,206 @@
+use mysql::{params, prelude::Queryable, Pool};
+
+use crate::Error;
+
+/// Records the posted updates as pending.
+/// Older updates of the same items that were not handled yet are superseded by them.
+/// If the guild has an escalation rule, a reminder is scheduled for every update.
+pub fn add_events(pool: &Pool, guild_id: u64, items: &[(u64, u64)]) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_batch(
+        r"UPDATE UpdateEvents SET Status = 'superseded' WHERE ServerId = :guild_id AND ItemId = :item_id AND Version < :version AND Status != 'handled';",
+        items.iter().map(|(item_id, version)| {
+            params! {
+                "guild_id" => guild_id,
+                "item_id" => item_id,
+                "version" => version,
+            }
+        }),
+    )?;
+
+    conn.exec_batch(
+        r"INSERT IGNORE INTO UpdateEvents (ServerId, ItemId, Version, PostedAt) VALUES (:guild_id, :item_id, :version, UNIX_TIMESTAMP());",
+        items.iter().map(|(item_id, version)| {
+            params! {
+                "guild_id" => guild_id,
+                "item_id" => item_id,
+                "version" => version,
+            }
+        }),
+    )?;
+
+    conn.exec_batch(
+        r"INSERT IGNORE INTO Reminders (ServerId, ItemId, Version, RemindAt) SELECT ServerId, :item_id, :version, UNIX_TIMESTAMP() + Hours * 3600 FROM EscalationRules WHERE ServerId = :guild_id AND (GroupId IS NULL OR GroupId IN (SELECT GroupId FROM GroupMembers WHERE ItemId = :item_id));",
+        items.iter().map(|(item_id, version)| {
+            params! {
+                "guild_id" => guild_id,
+                "item_id" => item_id,
+                "version" => version,
+            }
+        }),
+    )?;
+    Ok(())
+}
+
+pub fn mark_handled(
+    pool: &Pool,
+    guild_id: u64,
+    item_id: u64,
+    version: u64,
+    user_id: u64,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE UpdateEvents SET Status = 'handled', HandledBy = :user_id, HandledAt = UNIX_TIMESTAMP() WHERE ServerId = :guild_id AND ItemId = :item_id AND Version = :version;",
+        params! {
+            "user_id" => user_id,
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+            "version" => version,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn snooze(
+    pool: &Pool,
+    guild_id: u64,
+    item_id: u64,
+    version: u64,
+    user_id: u64,
+    until: u64,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE UpdateEvents SET Status = 'snoozed', HandledBy = :user_id, SnoozedUntil = :until WHERE ServerId = :guild_id AND ItemId = :item_id AND Version = :version AND Status != 'handled';",
+        params! {
+            "user_id" => user_id,
+            "until" => until,
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+            "version" => version,
+        },
+    )?;
+
+    // Nobody should be reminded while the update is snoozed
+    conn.exec_drop(
+        r"UPDATE Reminders SET RemindAt = GREATEST(RemindAt, :until) WHERE ServerId = :guild_id AND ItemId = :item_id AND Version = :version;",
+        params! {
+            "until" => until,
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+            "version" => version,
+        },
+    )?;
+    Ok(())
+}
+
+/// Returns the name, id, version and post time of every update nobody handled yet.
+/// Snoozed updates are included again once the snooze is over.
+pub fn get_pending(pool: &Pool, guild_id: u64) -> Result<Vec<(String, u64, u64, u64)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(String, u64, u64, u64)> = conn.query(format!(
+        "SELECT Items.ItemName, Items.ItemId, UpdateEvents.Version, UpdateEvents.PostedAt FROM UpdateEvents INNER JOIN Items ON UpdateEvents.ItemId = Items.ItemId INNER JOIN Subscriptions ON UpdateEvents.ServerId = Subscriptions.ServerId AND UpdateEvents.ItemId = Subscriptions.ItemId WHERE UpdateEvents.ServerId = {} AND Subscriptions.RemovedAt IS NULL AND (UpdateEvents.Status = 'pending' OR (UpdateEvents.Status = 'snoozed' AND UpdateEvents.SnoozedUntil <= UNIX_TIMESTAMP())) ORDER BY UpdateEvents.PostedAt",
+        guild_id
+    ))?;
+
+    Ok(res)
+}
+
+/// Returns the guild, item and version of every reminder that is due for an update nobody handled
+pub fn get_due_reminders(pool: &Pool) -> Result<Vec<(u64, u64, u64)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, u64, u64)> = conn.query(
+        "SELECT Reminders.ServerId, Reminders.ItemId, Reminders.Version FROM Reminders INNER JOIN UpdateEvents ON Reminders.ServerId = UpdateEvents.ServerId AND Reminders.ItemId = UpdateEvents.ItemId AND Reminders.Version = UpdateEvents.Version WHERE Reminders.RemindAt <= UNIX_TIMESTAMP() AND UpdateEvents.Status IN ('pending', 'snoozed')",
+    )?;
+
+    Ok(res)
+}
+
+/// Removes reminders that are sent or no longer needed because the update was handled
+pub fn remove_stale_reminders(pool: &Pool, reminders: &[(u64, u64, u64)]) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_batch(
+        r"DELETE FROM Reminders WHERE ServerId = :guild_id AND ItemId = :item_id AND Version = :version;",
+        reminders.iter().map(|(guild_id, item_id, version)| {
+            params! {
+                "guild_id" => guild_id,
+                "item_id" => item_id,
+                "version" => version,
+            }
+        }),
+    )?;
+
+    conn.query_drop(
+        "DELETE Reminders FROM Reminders INNER JOIN UpdateEvents ON Reminders.ServerId = UpdateEvents.ServerId AND Reminders.ItemId = UpdateEvents.ItemId AND Reminders.Version = UpdateEvents.Version WHERE UpdateEvents.Status IN ('handled', 'superseded')",
+    )?;
+    Ok(())
+}
+
+/// Only updates of items in the group are escalated if a group is given
+pub fn set_escalation_rule(
+    pool: &Pool,
+    guild_id: u64,
+    rule: Option<(u64, u64)>,
+    group_id: Option<u64>,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    match rule {
+        Some((hours, role_id)) => conn.exec_drop(
+            r"INSERT INTO EscalationRules (ServerId, Hours, RoleId, GroupId) VALUES (:guild_id, :hours, :role_id, :group_id) ON DUPLICATE KEY UPDATE Hours = :hours, RoleId = :role_id, GroupId = :group_id;",
+            params! {
+                "guild_id" => guild_id,
+                "hours" => hours,
+                "role_id" => role_id,
+                "group_id" => group_id,
+            },
+        )?,
+        None => conn.exec_drop(
+            r"DELETE FROM EscalationRules WHERE ServerId = :guild_id;",
+            params! {
+                "guild_id" => guild_id,
+            },
+        )?,
+    }
+    Ok(())
+}
+
+/// Returns the hours after which unhandled updates are escalated and the role to ping
+pub fn get_escalation_rule(pool: &Pool, guild_id: u64) -> Result<Option<(u64, u64)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<(u64, u64)> = conn.query_first(format!(
+        "SELECT Hours, RoleId FROM EscalationRules WHERE ServerId = {};",
+        guild_id
+    ))?;
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_pool;
+
+    #[test]
+    #[ignore = "needs a MySQL database in TEST_MYSQL_URL"]
+    fn escalation_rule_can_be_cleared() {
+        let (pool, guild_id) = test_pool();
+
+        set_escalation_rule(&pool, guild_id, Some((24, 42)), None).unwrap();
+        assert_eq!(
+            get_escalation_rule(&pool, guild_id).unwrap(),
+            Some((24, 42))
+        );
+
+        set_escalation_rule(&pool, guild_id, None, None).unwrap();
+        assert_eq!(get_escalation_rule(&pool, guild_id).unwrap(), None);
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::{HashMap, HashSet};
+
+use mysql::{params, prelude::Queryable, Pool};
+use sql_lexer::sanitize_string;
+
+use crate::Error;
+
+use super::Mention;
+
+pub fn create_group(pool: &Pool, guild_id: u64, name: &str) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"INSERT INTO ItemGroups (ServerId, Name) VALUES (:guild_id, :name);",
+        params! {
+            "guild_id" => guild_id,
+            "name" => sanitize_string(name.to_string()),
+        },
+    )?;
+    Ok(())
+}
+
+pub fn delete_group(pool: &Pool, group_id: u64) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"DELETE FROM ItemGroups WHERE GroupId = :group_id;",
+        params! {
+            "group_id" => group_id,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn get_group_id(pool: &Pool, guild_id: u64, name: &str) -> Result<Option<u64>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<u64> = conn.exec_first(
+        r"SELECT GroupId FROM ItemGroups WHERE ServerId = :guild_id AND Name = :name;",
+        params! {
+            "guild_id" => guild_id,
+            "name" => sanitize_string(name.to_string()),
+        },
+    )?;
+
+    Ok(res)
+}
+
+pub fn get_group_names(
+    pool: &Pool,
+    guild_id: u64,
+    query: Option<String>,
+) -> Result<Vec<String>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<String> = conn.exec(
+        r"SELECT Name FROM ItemGroups WHERE ServerId = :guild_id AND Name LIKE :query ORDER BY Name;",
+        params! {
+            "guild_id" => guild_id,
+            "query" => format!("%{}%", query.map(sanitize_string).unwrap_or_default()),
+        },
+    )?;
+
+    Ok(res)
+}
+
+/// Returns the name of every group of a guild and how many items are in it
+pub fn get_groups(pool: &Pool, guild_id: u64) -> Result<Vec<(String, u64)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(String, u64)> = conn.query(format!(
+        "SELECT ItemGroups.Name, COUNT(Subscriptions.ItemId) FROM ItemGroups LEFT JOIN GroupMembers ON ItemGroups.GroupId = GroupMembers.GroupId LEFT JOIN Subscriptions ON GroupMembers.ServerId = Subscriptions.ServerId AND GroupMembers.ItemId = Subscriptions.ItemId AND Subscriptions.RemovedAt IS NULL WHERE ItemGroups.ServerId = {} GROUP BY ItemGroups.GroupId, ItemGroups.Name ORDER BY ItemGroups.Name",
+        guild_id
+    ))?;
+
+    Ok(res)
+}
+
+pub fn add_member(pool: &Pool, group_id: u64, guild_id: u64, item_id: u64) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"INSERT IGNORE INTO GroupMembers (GroupId, ServerId, ItemId) VALUES (:group_id, :guild_id, :item_id);",
+        params! {
+            "group_id" => group_id,
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn remove_member(pool: &Pool, group_id: u64, item_id: u64) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"DELETE FROM GroupMembers WHERE GroupId = :group_id AND ItemId = :item_id;",
+        params! {
+            "group_id" => group_id,
+            "item_id" => item_id,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn get_group_items(pool: &Pool, group_id: u64) -> Result<HashSet<u64>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<u64> = conn.query(format!(
+        "SELECT ItemId FROM GroupMembers WHERE GroupId = {}",
+        group_id
+    ))?;
+
+    Ok(res.into_iter().collect())
+}
+
+/// Returns the names of the groups every item of a guild is in
+pub fn get_item_groups(pool: &Pool, guild_id: u64) -> Result<HashMap<u64, Vec<String>>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, String)> = conn.query(format!(
+        "SELECT GroupMembers.ItemId, ItemGroups.Name FROM GroupMembers INNER JOIN ItemGroups ON GroupMembers.GroupId = ItemGroups.GroupId WHERE GroupMembers.ServerId = {} ORDER BY ItemGroups.Name",
+        guild_id
+    ))?;
+
+    let mut groups: HashMap<u64, Vec<String>> = HashMap::new();
+    for (item_id, name) in res {
+        groups.entry(item_id).or_default().push(name);
+    }
+
+    Ok(groups)
+}
+
+pub fn set_group_mention(
+    pool: &Pool,
+    group_id: u64,
+    role_id: Option<u64>,
+    user_id: Option<u64>,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE ItemGroups SET MentionRole = :role_id, MentionUser = :user_id WHERE GroupId = :group_id;",
+        params! {
+            "role_id" => role_id,
+            "user_id" => user_id,
+            "group_id" => group_id,
+        },
+    )?;
+    Ok(())
+}
+
+/// Returns the name and the mentions of every group of a guild
+pub fn get_group_settings(
+    pool: &Pool,
+    guild_id: u64,
+) -> Result<Vec<(String, Vec<Mention>)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(String, Option<u64>, Option<u64>)> = conn.query(format!(
+        "SELECT Name, MentionRole, MentionUser FROM ItemGroups WHERE ServerId = {} ORDER BY Name",
+        guild_id
+    ))?;
+
+    Ok(res
+        .into_iter()
+        .map(|(name, role, user)| (name, Mention::from_columns(role, user)))
+        .collect())
+}
+
+/// Returns the mentions of the groups of every item in a guild
+pub fn get_group_mentions(pool: &Pool, guild_id: u64) -> Result<Vec<(u64, Mention)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, Option<u64>, Option<u64>)> = conn.query(format!(
+        "SELECT GroupMembers.ItemId, ItemGroups.MentionRole, ItemGroups.MentionUser FROM GroupMembers INNER JOIN ItemGroups ON GroupMembers.GroupId = ItemGroups.GroupId WHERE GroupMembers.ServerId = {} AND (ItemGroups.MentionRole IS NOT NULL OR ItemGroups.MentionUser IS NOT NULL)",
+        guild_id
+    ))?;
+
+    Ok(res
+        .into_iter()
+        .flat_map(|(item_id, role, user)| {
+            Mention::from_columns(role, user)
+                .into_iter()
+                .map(move |m| (item_id, m))
+        })
+        .collect())
+}
@@ -6,18 +6,23 @@ use crate::Error;
 
 use super::ItemInfo;
 
+type ItemRow = (u64, String, u64, Option<String>, Option<u64>);
+
 pub fn get_item(pool: &Pool, item_id: u64) -> Result<Option<ItemInfo>, Error> {
     let mut conn = pool.get_conn()?;
 
-    let res: Option<(u64, String, u64, Option<String>)> =
-        conn.query_first(format!("SELECT * FROM Items WHERE ItemId = {};", item_id))?;
+    let res: Option<ItemRow> = conn.query_first(format!(
+        "SELECT ItemId, ItemName, LastUpdate, PreviewUrl, FileSize FROM Items WHERE ItemId = {};",
+        item_id
+    ))?;
 
     match res {
-        Some((id, name, last_updated, preview_url)) => Ok(Some(ItemInfo {
+        Some((id, name, last_updated, preview_url, file_size)) => Ok(Some(ItemInfo {
             id,
             name,
             last_updated,
             preview_url,
+            file_size,
         })),
         None => Ok(None),
     }
@@ -27,13 +32,14 @@ pub fn add_item(pool: &Pool, info: ItemInfo) -> Result<(), Error> {
     let mut conn = pool.get_conn()?;
 
     let res = conn.exec_drop(
-        r"INSERT INTO Items (ItemId, ItemName, LastUpdate, PreviewUrl) VALUES (:id, :name, :last_update, :preview_url);",
+        r"INSERT INTO Items (ItemId, ItemName, LastUpdate, PreviewUrl, FileSize) VALUES (:id, :name, :last_update, :preview_url, :file_size);",
         params! {
             "id" => info.id,
             "name" => sanitize_string(info.name),
             "last_update" => info.last_updated,
             // We don't sanitize the preview url because it is already sanitized by steam
             "preview_url" => info.preview_url,
+            "file_size" => info.file_size,
         },
     );
 
@@ -50,13 +56,14 @@ pub fn update_item(pool: &Pool, info: ItemInfo) -> Result<(), Error> {
     let mut conn = pool.get_conn()?;
 
     let res = conn.exec_drop(
-        r"UPDATE Items SET ItemName = :name, LastUpdate = :last_update, PreviewUrl = :preview_url WHERE ItemId = :id;",
+        r"UPDATE Items SET ItemName = :name, LastUpdate = :last_update, PreviewUrl = :preview_url, FileSize = :file_size WHERE ItemId = :id;",
         params! {
             "id" => info.id,
             "name" => sanitize_string(info.name),
             "last_update" => info.last_updated,
             // We don't sanitize the preview url because it is already sanitized by steam
             "preview_url" => info.preview_url,
+            "file_size" => info.file_size,
         },
     );
 
@@ -74,17 +81,18 @@ pub fn get_item_by_name(pool: &Pool, name: &str) -> Result<ItemInfo, Error> {
 
     let name = sanitize_string(name.to_string());
 
-    let res: Option<(u64, String, u64, Option<String>)> = conn.query_first(format!(
-        "SELECT * FROM Items WHERE ItemName LIKE '%{}%';",
+    let res: Option<ItemRow> = conn.query_first(format!(
+        "SELECT ItemId, ItemName, LastUpdate, PreviewUrl, FileSize FROM Items WHERE ItemName LIKE '%{}%';",
         name
     ))?;
 
     match res {
-        Some((id, name, last_updated, preview_url)) => Ok(ItemInfo {
+        Some((id, name, last_updated, preview_url, file_size)) => Ok(ItemInfo {
             id,
             name,
             last_updated,
             preview_url,
+            file_size,
         }),
         None => Err("Item not found".into()),
     }
@@ -101,11 +109,11 @@ pub fn get_subscribed_item_names(
 
     let res: Vec<(String, u64)> = match query {
         Some(query) => conn.query(format!(
-            "SELECT ItemName, ItemId FROM Items WHERE ItemId IN (SELECT ItemId FROM Subscriptions WHERE ServerId = {}) AND ItemName LIKE '%{}%';",
+            "SELECT ItemName, ItemId FROM Items WHERE ItemId IN (SELECT ItemId FROM Subscriptions WHERE ServerId = {} AND RemovedAt IS NULL) AND ItemName LIKE '%{}%';",
             guild_id, query
         ))?,
         None => conn.query(format!(
-            "SELECT ItemName, ItemId FROM Items WHERE ItemId IN (SELECT ItemId FROM Subscriptions WHERE ServerId = {});",
+            "SELECT ItemName, ItemId FROM Items WHERE ItemId IN (SELECT ItemId FROM Subscriptions WHERE ServerId = {} AND RemovedAt IS NULL);",
             guild_id
         ))?,
     };
@@ -0,0 +1,220 @@
+use mysql::{params, prelude::Queryable, Pool, PooledConn};
+
+use crate::Error;
+
+/// Columns added after the first release, as (table, column, definition).
+/// `db/init.sql` only runs on an empty volume, so databases created by an older
+/// version get them from here. Keep this in sync with `db/init.sql`.
+const COLUMNS: &[(&str, &str, &str)] = &[
+    ("Servers", "MentionRole", "BIGINT NULL"),
+    ("Servers", "MentionUser", "BIGINT NULL"),
+    ("Servers", "WebhookUrl", "VARCHAR(2083) NULL"),
+    ("Servers", "WebhookName", "varchar(80) NULL"),
+    ("Servers", "WebhookAvatar", "VARCHAR(2083) NULL"),
+    ("Servers", "HookUrl", "VARCHAR(2083) NULL"),
+    ("Servers", "HookSecret", "varchar(128) NULL"),
+    (
+        "Servers",
+        "NotificationMode",
+        "varchar(10) DEFAULT 'immediate' NOT NULL",
+    ),
+    ("Servers", "DigestHour", "TINYINT NULL"),
+    ("Servers", "DigestDay", "TINYINT NULL"),
+    ("Servers", "LastDigest", "BIGINT NULL"),
+    ("Servers", "HeaderTemplate", "varchar(1000) NULL"),
+    ("Servers", "ItemTemplate", "varchar(1000) NULL"),
+    ("Servers", "LastRestart", "BIGINT NULL"),
+    ("Servers", "Timezone", "varchar(64) NULL"),
+    ("Servers", "Language", "varchar(8) NULL"),
+    ("Servers", "ManagerRole", "BIGINT NULL"),
+    ("Servers", "ContributorRole", "BIGINT NULL"),
+    ("Items", "FileSize", "BIGINT NULL"),
+    ("Watchers", "Language", "varchar(8) NULL"),
+    ("Subscriptions", "MentionRole", "BIGINT NULL"),
+    ("Subscriptions", "MentionUser", "BIGINT NULL"),
+    ("Subscriptions", "DeployedVersion", "BIGINT NULL"),
+    ("Subscriptions", "Muted", "BOOLEAN DEFAULT FALSE NOT NULL"),
+    ("Subscriptions", "MutedUntil", "BIGINT NULL"),
+    ("Subscriptions", "CheckInterval", "INT NULL"),
+    ("Subscriptions", "LastChecked", "BIGINT NULL"),
+    ("Subscriptions", "AddedAt", "BIGINT NULL"),
+    ("Subscriptions", "AddedBy", "BIGINT NULL"),
+    ("Subscriptions", "AddedFrom", "BIGINT NULL"),
+    ("Subscriptions", "RemovedAt", "BIGINT NULL"),
+    ("Subscriptions", "RemovalBatch", "BIGINT NULL"),
+];
+
+/// Tables added after the first release, in the order their foreign keys need
+const TABLES: &[&str] = &[
+    r"CREATE TABLE IF NOT EXISTS PendingDigests (
+	ServerId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	DetectedAt BIGINT NOT NULL,
+	CONSTRAINT PendingDigests_PK PRIMARY KEY (ServerId,ItemId,DetectedAt),
+	CONSTRAINT PendingDigests_FK FOREIGN KEY (ItemId) REFERENCES Items(ItemId) ON DELETE CASCADE ON UPDATE CASCADE,
+	CONSTRAINT PendingDigests_FK_1 FOREIGN KEY (ServerId) REFERENCES Servers(ServerId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;",
+    r"CREATE TABLE IF NOT EXISTS Watchers (
+	UserId BIGINT NOT NULL,
+	DmClosed BOOLEAN DEFAULT FALSE NOT NULL,
+	Language varchar(8) NULL,
+	CONSTRAINT Watchers_PK PRIMARY KEY (UserId)
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;",
+    r"CREATE TABLE IF NOT EXISTS Watches (
+	UserId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	LastNotified BIGINT NOT NULL,
+	CONSTRAINT Watches_PK PRIMARY KEY (UserId,ItemId),
+	CONSTRAINT Watches_FK FOREIGN KEY (ItemId) REFERENCES Items(ItemId) ON DELETE CASCADE ON UPDATE CASCADE,
+	CONSTRAINT Watches_FK_1 FOREIGN KEY (UserId) REFERENCES Watchers(UserId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;",
+    r"CREATE TABLE IF NOT EXISTS UpdateEvents (
+	ServerId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	Version BIGINT NOT NULL,
+	PostedAt BIGINT NOT NULL,
+	Status varchar(10) DEFAULT 'pending' NOT NULL,
+	HandledBy BIGINT NULL,
+	HandledAt BIGINT NULL,
+	SnoozedUntil BIGINT NULL,
+	CONSTRAINT UpdateEvents_PK PRIMARY KEY (ServerId,ItemId,Version),
+	CONSTRAINT UpdateEvents_FK FOREIGN KEY (ItemId) REFERENCES Items(ItemId) ON DELETE CASCADE ON UPDATE CASCADE,
+	CONSTRAINT UpdateEvents_FK_1 FOREIGN KEY (ServerId) REFERENCES Servers(ServerId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;",
+    r"CREATE TABLE IF NOT EXISTS ItemGroups (
+	GroupId BIGINT NOT NULL AUTO_INCREMENT,
+	ServerId BIGINT NOT NULL,
+	Name varchar(100) NOT NULL,
+	MentionRole BIGINT NULL,
+	MentionUser BIGINT NULL,
+	CONSTRAINT ItemGroups_PK PRIMARY KEY (GroupId),
+	CONSTRAINT ItemGroups_UN UNIQUE KEY (ServerId,Name),
+	CONSTRAINT ItemGroups_FK FOREIGN KEY (ServerId) REFERENCES Servers(ServerId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;",
+    r"CREATE TABLE IF NOT EXISTS GroupMembers (
+	GroupId BIGINT NOT NULL,
+	ServerId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	CONSTRAINT GroupMembers_PK PRIMARY KEY (GroupId,ItemId),
+	CONSTRAINT GroupMembers_FK FOREIGN KEY (GroupId) REFERENCES ItemGroups(GroupId) ON DELETE CASCADE ON UPDATE CASCADE,
+	CONSTRAINT GroupMembers_FK_1 FOREIGN KEY (ServerId,ItemId) REFERENCES Subscriptions(ServerId,ItemId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;",
+    r"CREATE TABLE IF NOT EXISTS EscalationRules (
+	ServerId BIGINT NOT NULL,
+	Hours INT NOT NULL,
+	RoleId BIGINT NOT NULL,
+	GroupId BIGINT NULL,
+	CONSTRAINT EscalationRules_PK PRIMARY KEY (ServerId),
+	CONSTRAINT EscalationRules_FK FOREIGN KEY (ServerId) REFERENCES Servers(ServerId) ON DELETE CASCADE ON UPDATE CASCADE,
+	CONSTRAINT EscalationRules_FK_1 FOREIGN KEY (GroupId) REFERENCES ItemGroups(GroupId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;",
+    r"CREATE TABLE IF NOT EXISTS Reminders (
+	ServerId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	Version BIGINT NOT NULL,
+	RemindAt BIGINT NOT NULL,
+	CONSTRAINT Reminders_PK PRIMARY KEY (ServerId,ItemId,Version),
+	CONSTRAINT Reminders_FK FOREIGN KEY (ServerId, ItemId, Version) REFERENCES UpdateEvents(ServerId, ItemId, Version) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;",
+    r"CREATE TABLE IF NOT EXISTS AuditLog (
+	AuditId BIGINT NOT NULL AUTO_INCREMENT,
+	ServerId BIGINT NOT NULL,
+	UserId BIGINT NOT NULL,
+	Command varchar(32) NOT NULL,
+	ItemId BIGINT NULL,
+	Field varchar(32) NOT NULL,
+	OldValue varchar(500) NULL,
+	NewValue varchar(500) NULL,
+	CreatedAt BIGINT NOT NULL,
+	CONSTRAINT AuditLog_PK PRIMARY KEY (AuditId),
+	CONSTRAINT AuditLog_FK FOREIGN KEY (ServerId) REFERENCES Servers(ServerId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;",
+    r"CREATE TABLE IF NOT EXISTS Notes (
+	NoteId BIGINT NOT NULL AUTO_INCREMENT,
+	ServerId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	Content varchar(500) NOT NULL,
+	AuthorId BIGINT NULL,
+	CreatedAt BIGINT NOT NULL,
+	RemovedBy BIGINT NULL,
+	RemovedAt BIGINT NULL,
+	CONSTRAINT Notes_PK PRIMARY KEY (NoteId),
+	CONSTRAINT Notes_FK FOREIGN KEY (ServerId,ItemId) REFERENCES Subscriptions(ServerId,ItemId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;",
+];
+
+/// Brings a database created by an older version up to date.
+/// Every step checks first, so this is safe to run on every start.
+pub fn migrate(pool: &Pool) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    // New tables first, columns may be added to tables that were added later
+    for table in TABLES {
+        conn.query_drop(table)?;
+    }
+
+    for (table, column, definition) in COLUMNS {
+        if column_type(&mut conn, table, column)?.is_none() {
+            conn.query_drop(format!(
+                "ALTER TABLE {} ADD COLUMN {} {};",
+                table, column, definition
+            ))?;
+        }
+    }
+
+    // Notes used to be limited to 500 characters, now the latest few are kept together
+    if column_type(&mut conn, "Subscriptions", "Note")?.as_deref() != Some("text") {
+        conn.query_drop(r"ALTER TABLE Subscriptions MODIFY Note TEXT NULL;")?;
+    }
+
+    // Notes written before the history was kept only exist on the subscription.
+    // They become the first entry of the history, without an author.
+    conn.query_drop(
+        r"INSERT INTO Notes (ServerId, ItemId, Content, CreatedAt) SELECT ServerId, ItemId, LEFT(Note, 500), COALESCE(AddedAt, UNIX_TIMESTAMP()) FROM Subscriptions WHERE Note IS NOT NULL AND NOT EXISTS (SELECT 1 FROM Notes WHERE Notes.ServerId = Subscriptions.ServerId AND Notes.ItemId = Subscriptions.ItemId);",
+    )?;
+
+    Ok(())
+}
+
+/// Returns the lower case type of a column, or none if it doesn't exist
+fn column_type(conn: &mut PooledConn, table: &str, column: &str) -> Result<Option<String>, Error> {
+    let res: Option<String> = conn.exec_first(
+        r"SELECT LOWER(DATA_TYPE) FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = :table AND COLUMN_NAME = :column",
+        params! {
+            "table" => table,
+            "column" => column,
+        },
+    )?;
+
+    Ok(res)
+}
@@ -0,0 +1,177 @@
+use mysql::{params, prelude::Queryable, Pool, Transaction, TxOpts};
+
+use crate::Error;
+
+/// How many of the latest notes are shown with an item
+pub const NOTES_SHOWN: usize = 3;
+
+/// A note on an item, removed notes are kept in the history
+#[derive(Debug, Clone)]
+pub struct Note {
+    pub id: u64,
+    pub content: String,
+    /// Unknown for notes written before authors were recorded
+    pub author_id: Option<u64>,
+    pub created_at: u64,
+    pub removed: Option<(u64, u64)>,
+}
+
+pub fn add_note(
+    pool: &Pool,
+    guild_id: u64,
+    item_id: u64,
+    author_id: u64,
+    content: &str,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+    let mut tx = conn.start_transaction(TxOpts::default())?;
+
+    tx.exec_drop(
+        r"INSERT INTO Notes (ServerId, ItemId, Content, AuthorId, CreatedAt) VALUES (:guild_id, :item_id, :content, :author_id, UNIX_TIMESTAMP());",
+        params! {
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+            "content" => content,
+            "author_id" => author_id,
+        },
+    )?;
+
+    update_latest_notes(&mut tx, guild_id, item_id)?;
+
+    tx.commit()?;
+    Ok(())
+}
+
+/// Returns false if the item has no such note, or it was already removed
+pub fn remove_note(
+    pool: &Pool,
+    guild_id: u64,
+    item_id: u64,
+    note_id: u64,
+    user_id: u64,
+) -> Result<bool, Error> {
+    let mut conn = pool.get_conn()?;
+    let mut tx = conn.start_transaction(TxOpts::default())?;
+
+    tx.exec_drop(
+        r"UPDATE Notes SET RemovedBy = :user_id, RemovedAt = UNIX_TIMESTAMP() WHERE NoteId = :note_id AND ServerId = :guild_id AND ItemId = :item_id AND RemovedAt IS NULL;",
+        params! {
+            "user_id" => user_id,
+            "note_id" => note_id,
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+        },
+    )?;
+
+    if tx.affected_rows() == 0 {
+        return Ok(false);
+    }
+
+    update_latest_notes(&mut tx, guild_id, item_id)?;
+
+    tx.commit()?;
+    Ok(true)
+}
+
+/// Returns every note ever written on an item, newest first
+#[allow(clippy::type_complexity)]
+pub fn get_history(pool: &Pool, guild_id: u64, item_id: u64) -> Result<Vec<Note>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, String, Option<u64>, u64, Option<u64>, Option<u64>)> = conn.exec(
+        r"SELECT NoteId, Content, AuthorId, CreatedAt, RemovedBy, RemovedAt FROM Notes WHERE ServerId = :guild_id AND ItemId = :item_id ORDER BY NoteId DESC",
+        params! {
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+        },
+    )?;
+
+    Ok(res
+        .into_iter()
+        .map(
+            |(id, content, author_id, created_at, removed_by, removed_at)| Note {
+                id,
+                content,
+                author_id,
+                created_at,
+                removed: removed_by.zip(removed_at),
+            },
+        )
+        .collect())
+}
+
+/// Returns the id and content of every note shown with the items of a guild, by item
+pub fn get_active_notes(pool: &Pool, guild_id: u64) -> Result<Vec<(u64, u64, String)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, u64, String)> = conn.query(format!(
+        "SELECT ItemId, NoteId, Content FROM Notes WHERE ServerId = {} AND RemovedAt IS NULL ORDER BY NoteId",
+        guild_id
+    ))?;
+
+    Ok(res)
+}
+
+/// The subscription keeps the latest notes, so everything showing an item can read them from there
+fn update_latest_notes(tx: &mut Transaction, guild_id: u64, item_id: u64) -> Result<(), Error> {
+    let latest: Vec<String> = tx.exec(
+        r"SELECT Content FROM Notes WHERE ServerId = :guild_id AND ItemId = :item_id AND RemovedAt IS NULL ORDER BY NoteId DESC LIMIT :limit",
+        params! {
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+            "limit" => NOTES_SHOWN,
+        },
+    )?;
+
+    let note = (!latest.is_empty()).then(|| latest.join("\n"));
+
+    tx.exec_drop(
+        r"UPDATE Subscriptions SET Note = :note WHERE ServerId = :guild_id AND ItemId = :item_id;",
+        params! {
+            "note" => note,
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+        },
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_pool;
+
+    #[test]
+    #[ignore = "needs a MySQL database in TEST_MYSQL_URL"]
+    fn removed_notes_leave_the_latest_ones() {
+        let (pool, guild_id) = test_pool();
+        let item_id = guild_id;
+
+        let mut conn = pool.get_conn().unwrap();
+        conn.query_drop(format!(
+            "INSERT IGNORE INTO Items (ItemId, ItemName, LastUpdate) VALUES ({}, 'Test', 0);",
+            item_id
+        ))
+        .unwrap();
+        conn.query_drop(format!(
+            "INSERT INTO Subscriptions (ServerId, ItemId, LastUpdate) VALUES ({}, {}, 0);",
+            guild_id, item_id
+        ))
+        .unwrap();
+
+        add_note(&pool, guild_id, item_id, 1, "first").unwrap();
+        add_note(&pool, guild_id, item_id, 1, "second").unwrap();
+
+        let first = get_active_notes(&pool, guild_id).unwrap()[0].1;
+        assert!(remove_note(&pool, guild_id, item_id, first, 2).unwrap());
+        assert!(!remove_note(&pool, guild_id, item_id, first, 2).unwrap());
+
+        let active: Vec<String> = get_active_notes(&pool, guild_id)
+            .unwrap()
+            .into_iter()
+            .map(|(_, _, content)| content)
+            .collect();
+        assert_eq!(active, vec!["second".to_string()]);
+        assert_eq!(get_history(&pool, guild_id, item_id).unwrap().len(), 2);
+    }
+}
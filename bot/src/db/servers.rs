@@ -1,7 +1,15 @@
+use chrono_tz::Tz;
 use mysql::{params, prelude::Queryable, Pool};
 use poise::serenity_prelude::Guild;
+use sql_lexer::sanitize_string;
 
-use crate::Error;
+use crate::{
+    i18n::Language,
+    template::{TemplateKind, Templates},
+    Error,
+};
+
+use super::{Mention, NotificationMode, PermissionLevel};
 
 pub fn add_server(pool: &Pool, guild: &Guild) -> Result<(), Error> {
     let mut conn = pool.get_conn()?;
@@ -125,3 +133,370 @@ pub fn get_last_update(pool: &Pool, guild_id: u64) -> Result<Option<u64>, Error>
 
     Ok(res.flatten())
 }
+
+pub fn update_last_restart_timestamp(pool: &Pool, guild_id: u64) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Servers SET LastRestart = UNIX_TIMESTAMP() WHERE ServerId = :id;",
+        params! {
+            "id" => guild_id,
+        },
+    )?;
+
+    Ok(())
+}
+
+/// When the tracking job of a guild was last started
+pub fn get_last_restart(pool: &Pool, guild_id: u64) -> Result<Option<u64>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<Option<u64>> = conn.query_first(format!(
+        "SELECT LastRestart FROM Servers WHERE ServerId = {};",
+        guild_id
+    ))?;
+
+    Ok(res.flatten())
+}
+
+pub fn set_timezone(pool: &Pool, guild_id: u64, timezone: Option<Tz>) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Servers SET Timezone = :timezone WHERE ServerId = :id;",
+        params! {
+            "timezone" => timezone.map(|tz| tz.name()),
+            "id" => guild_id,
+        },
+    )?;
+
+    Ok(())
+}
+
+pub fn set_language(pool: &Pool, guild_id: u64, language: Language) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Servers SET Language = :language WHERE ServerId = :id;",
+        params! {
+            "language" => language.as_str(),
+            "id" => guild_id,
+        },
+    )?;
+
+    Ok(())
+}
+
+/// The language the bot answers a guild in, english if none is set
+pub fn get_language(pool: &Pool, guild_id: u64) -> Result<Language, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<Option<String>> = conn.query_first(format!(
+        "SELECT Language FROM Servers WHERE ServerId = {};",
+        guild_id
+    ))?;
+
+    match res.flatten() {
+        Some(language) => Language::from_db(&language),
+        None => Ok(Language::English),
+    }
+}
+
+/// The timezone dates of a guild are in, UTC if none is set
+pub fn get_timezone(pool: &Pool, guild_id: u64) -> Result<Tz, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<Option<String>> = conn.query_first(format!(
+        "SELECT Timezone FROM Servers WHERE ServerId = {};",
+        guild_id
+    ))?;
+
+    Ok(res
+        .flatten()
+        .and_then(|tz| tz.parse().ok())
+        .unwrap_or(Tz::UTC))
+}
+
+pub fn set_mention(
+    pool: &Pool,
+    guild_id: u64,
+    role_id: Option<u64>,
+    user_id: Option<u64>,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Servers SET MentionRole = :role_id, MentionUser = :user_id WHERE ServerId = :id;",
+        params! {
+            "role_id" => role_id,
+            "user_id" => user_id,
+            "id" => guild_id,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn get_mentions(pool: &Pool, guild_id: u64) -> Result<Vec<Mention>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<(Option<u64>, Option<u64>)> = conn.query_first(format!(
+        "SELECT MentionRole, MentionUser FROM Servers WHERE ServerId = {};",
+        guild_id
+    ))?;
+
+    Ok(res
+        .map(|(role, user)| Mention::from_columns(role, user))
+        .unwrap_or_default())
+}
+
+pub fn set_permission_role(
+    pool: &Pool,
+    guild_id: u64,
+    level: PermissionLevel,
+    role_id: Option<u64>,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    let column = match level {
+        PermissionLevel::Manager => "ManagerRole",
+        PermissionLevel::Contributor => "ContributorRole",
+    };
+
+    conn.exec_drop(
+        format!(
+            "UPDATE Servers SET {} = :role_id WHERE ServerId = :id;",
+            column
+        ),
+        params! {
+            "role_id" => role_id,
+            "id" => guild_id,
+        },
+    )?;
+    Ok(())
+}
+
+/// Returns the manager and the contributor role of a guild
+pub fn get_permission_roles(
+    pool: &Pool,
+    guild_id: u64,
+) -> Result<(Option<u64>, Option<u64>), Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<(Option<u64>, Option<u64>)> = conn.query_first(format!(
+        "SELECT ManagerRole, ContributorRole FROM Servers WHERE ServerId = {};",
+        guild_id
+    ))?;
+
+    Ok(res.unwrap_or_default())
+}
+
+pub fn set_webhook(
+    pool: &Pool,
+    guild_id: u64,
+    url: Option<String>,
+    name: Option<String>,
+    avatar_url: Option<String>,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Servers SET WebhookUrl = :url, WebhookName = :name, WebhookAvatar = :avatar_url WHERE ServerId = :id;",
+        params! {
+            "url" => url,
+            "name" => name.map(sanitize_string),
+            "avatar_url" => avatar_url,
+            "id" => guild_id,
+        },
+    )?;
+    Ok(())
+}
+
+/// Returns the url, username and avatar url of the webhook of a guild
+#[allow(clippy::type_complexity)]
+pub fn get_webhook(
+    pool: &Pool,
+    guild_id: u64,
+) -> Result<Option<(String, Option<String>, Option<String>)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<(Option<String>, Option<String>, Option<String>)> =
+        conn.query_first(format!(
+            "SELECT WebhookUrl, WebhookName, WebhookAvatar FROM Servers WHERE ServerId = {};",
+            guild_id
+        ))?;
+
+    Ok(res.and_then(|(url, name, avatar_url)| url.map(|url| (url, name, avatar_url))))
+}
+
+pub fn set_hook(
+    pool: &Pool,
+    guild_id: u64,
+    url: Option<String>,
+    secret: Option<String>,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Servers SET HookUrl = :url, HookSecret = :secret WHERE ServerId = :id;",
+        params! {
+            "url" => url,
+            "secret" => secret,
+            "id" => guild_id,
+        },
+    )?;
+    Ok(())
+}
+
+/// Returns the url and the signing secret of the outbound hook of a guild
+pub fn get_hook(pool: &Pool, guild_id: u64) -> Result<Option<(String, String)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<(Option<String>, Option<String>)> = conn.query_first(format!(
+        "SELECT HookUrl, HookSecret FROM Servers WHERE ServerId = {};",
+        guild_id
+    ))?;
+
+    Ok(res.and_then(|(url, secret)| url.zip(secret)))
+}
+
+pub fn set_notification_mode(
+    pool: &Pool,
+    guild_id: u64,
+    mode: NotificationMode,
+    hour: Option<u8>,
+    day: Option<u8>,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Servers SET NotificationMode = :mode, DigestHour = :hour, DigestDay = :day WHERE ServerId = :id;",
+        params! {
+            "mode" => mode.as_str(),
+            "hour" => hour,
+            "day" => day,
+            "id" => guild_id,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn get_notification_mode(pool: &Pool, guild_id: u64) -> Result<NotificationMode, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<String> = conn.query_first(format!(
+        "SELECT NotificationMode FROM Servers WHERE ServerId = {};",
+        guild_id
+    ))?;
+
+    match res {
+        Some(mode) => NotificationMode::from_db(&mode),
+        None => Ok(NotificationMode::Immediate),
+    }
+}
+
+/// Returns the hour and the weekday a guild gets its digest at
+pub fn get_digest_time(pool: &Pool, guild_id: u64) -> Result<(Option<u8>, Option<u8>), Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<(Option<u8>, Option<u8>)> = conn.query_first(format!(
+        "SELECT DigestHour, DigestDay FROM Servers WHERE ServerId = {};",
+        guild_id
+    ))?;
+
+    Ok(res.unwrap_or_default())
+}
+
+/// Returns the mode, digest hour, digest day, timezone and last digest of every guild using digests
+#[allow(clippy::type_complexity)]
+pub fn get_digest_schedules(
+    pool: &Pool,
+) -> Result<Vec<(u64, NotificationMode, u8, u8, Tz, Option<u64>)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(
+        u64,
+        String,
+        Option<u8>,
+        Option<u8>,
+        Option<String>,
+        Option<u64>,
+    )> = conn.query(
+        "SELECT ServerId, NotificationMode, DigestHour, DigestDay, Timezone, LastDigest FROM Servers WHERE NotificationMode != 'immediate';",
+    )?;
+
+    res.into_iter()
+        .map(|(guild_id, mode, hour, day, timezone, last_digest)| {
+            Ok((
+                guild_id,
+                NotificationMode::from_db(&mode)?,
+                hour.unwrap_or(0),
+                day.unwrap_or(0),
+                timezone.and_then(|tz| tz.parse().ok()).unwrap_or(Tz::UTC),
+                last_digest,
+            ))
+        })
+        .collect()
+}
+
+pub fn update_last_digest_timestamp(pool: &Pool, guild_id: u64) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Servers SET LastDigest = UNIX_TIMESTAMP() WHERE ServerId = :id;",
+        params! {
+            "id" => guild_id,
+        },
+    )?;
+
+    Ok(())
+}
+
+pub fn get_last_digest(pool: &Pool, guild_id: u64) -> Result<Option<u64>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<Option<u64>> = conn.query_first(format!(
+        "SELECT LastDigest FROM Servers WHERE ServerId = {};",
+        guild_id
+    ))?;
+
+    Ok(res.flatten())
+}
+
+pub fn set_template(
+    pool: &Pool,
+    guild_id: u64,
+    kind: TemplateKind,
+    template: Option<String>,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    let column = match kind {
+        TemplateKind::Header => "HeaderTemplate",
+        TemplateKind::Item => "ItemTemplate",
+    };
+
+    conn.exec_drop(
+        format!(
+            "UPDATE Servers SET {} = :template WHERE ServerId = :id;",
+            column
+        ),
+        params! {
+            "template" => template,
+            "id" => guild_id,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn get_templates(pool: &Pool, guild_id: u64) -> Result<Templates, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<(Option<String>, Option<String>)> = conn.query_first(format!(
+        "SELECT HeaderTemplate, ItemTemplate FROM Servers WHERE ServerId = {};",
+        guild_id
+    ))?;
+
+    Ok(res
+        .map(|(header, item)| Templates { header, item })
+        .unwrap_or_default())
+}
@@ -1,9 +1,13 @@
-use mysql::{params, prelude::Queryable, Pool};
-use sql_lexer::sanitize_string;
+use std::{collections::HashMap, time};
+
+use mysql::{params, prelude::Queryable, Pool, TxOpts};
 
 use crate::Error;
 
-use super::ItemInfo;
+use super::{ItemInfo, Mention, Provenance};
+
+/// How long removed items can be restored, in seconds
+pub const TRASH_RETENTION: u64 = 60 * 60 * 24 * 7;
 
 #[allow(clippy::type_complexity)]
 pub fn get_all_subscriptions_of_guild(
@@ -12,14 +16,14 @@ pub fn get_all_subscriptions_of_guild(
 ) -> Result<Vec<(u64, ItemInfo, Option<String>)>, Error> {
     let mut conn = pool.get_conn()?;
 
-    let res: Vec<(u64, u64, String, u64, Option<String>, Option<String>)> = conn.query(format!(
-        "SELECT Subscriptions.LastUpdate, Items.ItemId,  Items.ItemName, Items.LastUpdate, Items.PreviewUrl, Subscriptions.Note FROM Subscriptions INNER JOIN Items ON Subscriptions.ItemId = Items.ItemId WHERE Subscriptions.ServerId = {}",
+    let res: Vec<(u64, u64, String, u64, Option<String>, Option<u64>, Option<String>)> = conn.query(format!(
+        "SELECT Subscriptions.LastUpdate, Items.ItemId,  Items.ItemName, Items.LastUpdate, Items.PreviewUrl, Items.FileSize, Subscriptions.Note FROM Subscriptions INNER JOIN Items ON Subscriptions.ItemId = Items.ItemId WHERE Subscriptions.ServerId = {} AND Subscriptions.RemovedAt IS NULL",
         guild_id
     ))?;
 
     res.into_iter()
         .map(
-            |(last_notified, id, name, last_updated, preview_url, note)| {
+            |(last_notified, id, name, last_updated, preview_url, file_size, note)| {
                 Ok((
                     last_notified,
                     ItemInfo {
@@ -27,6 +31,7 @@ pub fn get_all_subscriptions_of_guild(
                         name,
                         last_updated,
                         preview_url,
+                        file_size,
                     },
                     note,
                 ))
@@ -35,35 +40,192 @@ pub fn get_all_subscriptions_of_guild(
         .collect()
 }
 
+/// Returns every subscription of a guild that is due to be checked, or all if forced, with its check interval in minutes.
+/// Items without their own interval are checked on the schedule of the guild.
+#[allow(clippy::type_complexity)]
+pub fn get_due_subscriptions(
+    pool: &Pool,
+    guild_id: u64,
+    now: u64,
+    force: bool,
+) -> Result<Vec<(u64, ItemInfo, Option<String>, u64)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, u64, String, u64, Option<String>, Option<u64>, Option<String>, Option<u64>)> = conn.exec(
+        r"SELECT Subscriptions.LastUpdate, Items.ItemId, Items.ItemName, Items.LastUpdate, Items.PreviewUrl, Items.FileSize, Subscriptions.Note, COALESCE(Subscriptions.CheckInterval, Servers.Schedule * 60) FROM Subscriptions INNER JOIN Items ON Subscriptions.ItemId = Items.ItemId INNER JOIN Servers ON Subscriptions.ServerId = Servers.ServerId WHERE Subscriptions.ServerId = :guild_id AND Subscriptions.RemovedAt IS NULL AND (:force OR Subscriptions.LastChecked IS NULL OR Subscriptions.LastChecked + COALESCE(Subscriptions.CheckInterval, Servers.Schedule * 60) * 60 <= :now);",
+        params! {
+            "guild_id" => guild_id,
+            "now" => now,
+            "force" => force,
+        },
+    )?;
+
+    // Items without their own interval can't be checked without a schedule
+    Ok(res
+        .into_iter()
+        .filter_map(
+            |(last_notified, id, name, last_updated, preview_url, file_size, note, interval)| {
+                Some((
+                    last_notified,
+                    ItemInfo {
+                        id,
+                        name,
+                        last_updated,
+                        preview_url,
+                        file_size,
+                    },
+                    note,
+                    interval?,
+                ))
+            },
+        )
+        .collect())
+}
+
+pub fn update_last_checked(
+    pool: &Pool,
+    guild_id: u64,
+    item_ids: &[u64],
+    checked_at: u64,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_batch(
+        r"UPDATE Subscriptions SET LastChecked = :checked_at WHERE ServerId = :guild_id AND ItemId = :item_id;",
+        item_ids.iter().map(|item_id| {
+            params! {
+                "checked_at" => checked_at,
+                "guild_id" => guild_id,
+                "item_id" => item_id,
+            }
+        }),
+    )?;
+    Ok(())
+}
+
+/// Makes every item of a guild due to be checked
+pub fn reset_last_checked(pool: &Pool, guild_id: u64) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Subscriptions SET LastChecked = NULL WHERE ServerId = :guild_id;",
+        params! {
+            "guild_id" => guild_id,
+        },
+    )?;
+    Ok(())
+}
+
+/// Overrides how often an item is checked, `None` uses the schedule of the guild again
+pub fn set_check_interval(
+    pool: &Pool,
+    guild_id: u64,
+    item_id: u64,
+    minutes: Option<u64>,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Subscriptions SET CheckInterval = :minutes, LastChecked = NULL WHERE ServerId = :guild_id AND ItemId = :item_id;",
+        params! {
+            "minutes" => minutes,
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+        },
+    )?;
+    Ok(())
+}
+
+/// Returns the items of a guild with their own check interval in minutes
+pub fn get_check_intervals(pool: &Pool, guild_id: u64) -> Result<HashMap<u64, u64>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, u64)> = conn.query(format!(
+        "SELECT ItemId, CheckInterval FROM Subscriptions WHERE ServerId = {} AND RemovedAt IS NULL AND CheckInterval IS NOT NULL",
+        guild_id
+    ))?;
+
+    Ok(res.into_iter().collect())
+}
+
 pub fn check_subscription(pool: &Pool, guild_id: u64, item_id: u64) -> Result<bool, Error> {
     let mut conn = pool.get_conn()?;
 
     let res: Vec<(u64,)> = conn.query(format!(
-        "SELECT COUNT(*) FROM Subscriptions WHERE ServerId = {} AND ItemId = {}",
+        "SELECT COUNT(*) FROM Subscriptions WHERE ServerId = {} AND ItemId = {} AND RemovedAt IS NULL",
         guild_id, item_id
     ))?;
 
     Ok(res[0].0 > 0)
 }
 
-pub fn add_subscription(pool: &Pool, guild_id: u64, item_id: u64) -> Result<(), Error> {
+/// Adds an item, remembering who added it and from which collection
+pub fn add_subscription(
+    pool: &Pool,
+    guild_id: u64,
+    item_id: u64,
+    added_by: u64,
+    collection: Option<u64>,
+) -> Result<(), Error> {
     let mut conn = pool.get_conn()?;
+    let mut tx = conn.start_transaction(TxOpts::default())?;
 
-    conn.exec_drop(
-        r"INSERT INTO Subscriptions (ServerId, ItemId, LastUpdate) VALUES (:guild_id, :item_id, UNIX_TIMESTAMP());",
+    // Adding an item again starts fresh, use /undo_remove to get the old settings back
+    tx.exec_drop(
+        r"DELETE FROM Subscriptions WHERE ServerId = :guild_id AND ItemId = :item_id AND RemovedAt IS NOT NULL;",
+        params! {
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+        },
+    )?;
+
+    tx.exec_drop(
+        r"INSERT INTO Subscriptions (ServerId, ItemId, LastUpdate, AddedAt, AddedBy, AddedFrom) VALUES (:guild_id, :item_id, UNIX_TIMESTAMP(), UNIX_TIMESTAMP(), :added_by, :collection);",
         params! {
             "guild_id" => guild_id,
             "item_id" => item_id,
+            "added_by" => added_by,
+            "collection" => collection,
         },
     )?;
+
+    tx.commit()?;
     Ok(())
 }
 
+/// Moves an item to the trash, where it is kept with all its settings for [`TRASH_RETENTION`]
 pub fn remove_subscription(pool: &Pool, guild_id: u64, item_id: u64) -> Result<(), Error> {
+    remove_subscriptions(pool, guild_id, &[item_id])
+}
+
+/// Moves all given items to the trash at once, either all of them are removed or none.
+/// They can be restored together with /undo_remove.
+pub fn remove_subscriptions(pool: &Pool, guild_id: u64, item_ids: &[u64]) -> Result<(), Error> {
+    let batch = new_removal_batch()?;
+
+    let mut conn = pool.get_conn()?;
+    let mut tx = conn.start_transaction(TxOpts::default())?;
+
+    tx.exec_batch(
+        r"UPDATE Subscriptions SET RemovedAt = UNIX_TIMESTAMP(), RemovalBatch = :batch WHERE ServerId = :guild_id AND ItemId = :item_id AND RemovedAt IS NULL;",
+        item_ids.iter().map(|item_id| {
+            params! {
+                "batch" => batch,
+                "guild_id" => guild_id,
+                "item_id" => item_id,
+            }
+        }),
+    )?;
+
+    tx.commit()?;
+    Ok(())
+}
+
+pub fn update_last_notify(pool: &Pool, guild_id: u64, item_id: u64) -> Result<(), Error> {
     let mut conn = pool.get_conn()?;
 
     conn.exec_drop(
-        r"DELETE FROM Subscriptions WHERE ServerId = :guild_id AND ItemId = :item_id;",
+        r"UPDATE Subscriptions SET LastUpdate = UNIX_TIMESTAMP() WHERE ServerId = :guild_id AND ItemId = :item_id;",
         params! {
             "guild_id" => guild_id,
             "item_id" => item_id,
@@ -72,12 +234,20 @@ pub fn remove_subscription(pool: &Pool, guild_id: u64, item_id: u64) -> Result<(
     Ok(())
 }
 
-pub fn update_last_notify(pool: &Pool, guild_id: u64, item_id: u64) -> Result<(), Error> {
+pub fn set_item_mention(
+    pool: &Pool,
+    guild_id: u64,
+    item_id: u64,
+    role_id: Option<u64>,
+    user_id: Option<u64>,
+) -> Result<(), Error> {
     let mut conn = pool.get_conn()?;
 
     conn.exec_drop(
-        r"UPDATE Subscriptions SET LastUpdate = UNIX_TIMESTAMP() WHERE ServerId = :guild_id AND ItemId = :item_id;",
+        r"UPDATE Subscriptions SET MentionRole = :role_id, MentionUser = :user_id WHERE ServerId = :guild_id AND ItemId = :item_id;",
         params! {
+            "role_id" => role_id,
+            "user_id" => user_id,
             "guild_id" => guild_id,
             "item_id" => item_id,
         },
@@ -85,11 +255,29 @@ pub fn update_last_notify(pool: &Pool, guild_id: u64, item_id: u64) -> Result<()
     Ok(())
 }
 
+pub fn get_item_mentions(pool: &Pool, guild_id: u64) -> Result<Vec<(u64, Mention)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, Option<u64>, Option<u64>)> = conn.query(format!(
+        "SELECT ItemId, MentionRole, MentionUser FROM Subscriptions WHERE ServerId = {} AND RemovedAt IS NULL AND (MentionRole IS NOT NULL OR MentionUser IS NOT NULL)",
+        guild_id
+    ))?;
+
+    Ok(res
+        .into_iter()
+        .flat_map(|(item_id, role, user)| {
+            Mention::from_columns(role, user)
+                .into_iter()
+                .map(move |m| (item_id, m))
+        })
+        .collect())
+}
+
 pub fn count_guild_subscriptions(pool: &Pool, guild_id: u64) -> Result<u64, Error> {
     let mut conn = pool.get_conn()?;
 
     let res: Vec<(u64,)> = conn.query(format!(
-        "SELECT COUNT(*) FROM Subscriptions WHERE ServerId = {}",
+        "SELECT COUNT(*) FROM Subscriptions WHERE ServerId = {} AND RemovedAt IS NULL",
         guild_id
     ))?;
 
@@ -100,83 +288,311 @@ pub fn remove_all_subscriptions(pool: &Pool, guild_id: u64) -> Result<(), Error>
     let mut conn = pool.get_conn()?;
 
     conn.exec_drop(
-        r"DELETE FROM Subscriptions WHERE ServerId = :guild_id;",
+        r"UPDATE Subscriptions SET RemovedAt = UNIX_TIMESTAMP(), RemovalBatch = :batch WHERE ServerId = :guild_id AND RemovedAt IS NULL;",
         params! {
+            "batch" => new_removal_batch()?,
             "guild_id" => guild_id,
         },
     )?;
     Ok(())
 }
 
-pub fn get_note(pool: &Pool, guild_id: u64, item_id: u64) -> Result<Option<String>, Error> {
+/// Identifies the items removed together, so they can be restored together
+fn new_removal_batch() -> Result<u64, Error> {
+    Ok(time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)?
+        .as_millis() as u64)
+}
+
+/// Returns the trashed items of a guild, newest removal first, with when they were removed
+pub fn get_trash(pool: &Pool, guild_id: u64) -> Result<Vec<(String, u64, u64)>, Error> {
     let mut conn = pool.get_conn()?;
 
-    let res: Option<Option<String>> = conn.query_first(format!(
-        "SELECT Note FROM Subscriptions WHERE ServerId = {} AND ItemId = {}",
-        guild_id, item_id
-    ))?;
+    let res: Vec<(String, u64, u64)> = conn.exec(
+        r"SELECT Items.ItemName, Items.ItemId, Subscriptions.RemovedAt FROM Subscriptions INNER JOIN Items ON Subscriptions.ItemId = Items.ItemId WHERE Subscriptions.ServerId = :guild_id AND Subscriptions.RemovedAt > UNIX_TIMESTAMP() - :retention ORDER BY Subscriptions.RemovalBatch DESC, Items.ItemName;",
+        params! {
+            "guild_id" => guild_id,
+            "retention" => TRASH_RETENTION,
+        },
+    )?;
 
-    match res {
-        Some(note) => Ok(note),
-        None => Err("Not subscribed".into()),
-    }
+    Ok(res)
 }
 
-pub fn update_subscription_note(
+/// Restores a single trashed item, or all items of the latest removal if none is given.
+/// Returns how many items were restored.
+pub fn restore_subscriptions(
     pool: &Pool,
     guild_id: u64,
-    item_id: u64,
-    note: Option<String>,
-) -> Result<(), Error> {
+    item_id: Option<u64>,
+) -> Result<u64, Error> {
     let mut conn = pool.get_conn()?;
 
-    if note.is_none() || note.as_ref().unwrap().is_empty() {
-        conn.exec_drop(
-            r"UPDATE Subscriptions SET Note = NULL WHERE ServerId = :guild_id AND ItemId = :item_id;",
+    match item_id {
+        Some(item_id) => conn.exec_drop(
+            r"UPDATE Subscriptions SET RemovedAt = NULL, RemovalBatch = NULL WHERE ServerId = :guild_id AND ItemId = :item_id AND RemovedAt > UNIX_TIMESTAMP() - :retention;",
             params! {
                 "guild_id" => guild_id,
                 "item_id" => item_id,
+                "retention" => TRASH_RETENTION,
             },
-        )?;
-        return Ok(());
+        )?,
+        None => {
+            let batch: Option<u64> = conn.exec_first(
+                r"SELECT MAX(RemovalBatch) FROM Subscriptions WHERE ServerId = :guild_id AND RemovedAt > UNIX_TIMESTAMP() - :retention;",
+                params! {
+                    "guild_id" => guild_id,
+                    "retention" => TRASH_RETENTION,
+                },
+            )?
+            .flatten();
+
+            let batch = match batch {
+                Some(batch) => batch,
+                None => return Ok(0),
+            };
+
+            conn.exec_drop(
+                r"UPDATE Subscriptions SET RemovedAt = NULL, RemovalBatch = NULL WHERE ServerId = :guild_id AND RemovalBatch = :batch;",
+                params! {
+                    "guild_id" => guild_id,
+                    "batch" => batch,
+                },
+            )?
+        }
     }
 
+    Ok(conn.affected_rows())
+}
+
+/// Deletes everything that has been in the trash for longer than [`TRASH_RETENTION`]
+pub fn purge_trash(pool: &Pool) -> Result<u64, Error> {
+    let mut conn = pool.get_conn()?;
+
     conn.exec_drop(
-        r"UPDATE Subscriptions SET Note = :note WHERE ServerId = :guild_id AND ItemId = :item_id;",
+        r"DELETE FROM Subscriptions WHERE RemovedAt <= UNIX_TIMESTAMP() - :retention;",
         params! {
-            "note" => note.map(sanitize_string),
-            "guild_id" => guild_id,
-            "item_id" => item_id,
+            "retention" => TRASH_RETENTION,
         },
     )?;
-    Ok(())
+
+    Ok(conn.affected_rows())
+}
+
+pub fn get_note(pool: &Pool, guild_id: u64, item_id: u64) -> Result<Option<String>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Option<Option<String>> = conn.query_first(format!(
+        "SELECT Note FROM Subscriptions WHERE ServerId = {} AND ItemId = {} AND RemovedAt IS NULL",
+        guild_id, item_id
+    ))?;
+
+    match res {
+        Some(note) => Ok(note),
+        None => Err("Not subscribed".into()),
+    }
 }
 
+/// Returns the items that changed between the two timestamps, the most recent update first
+#[allow(clippy::type_complexity)]
 pub async fn get_changes_since(
     pool: &Pool,
     guild_id: u64,
     since: u64,
+    until: u64,
 ) -> Result<Vec<(ItemInfo, Option<String>)>, Error> {
     let mut conn = pool.get_conn()?;
 
-    let res: Vec<(u64, String, u64, Option<String>, Option<String>)> = conn.query(format!(
-        "SELECT Items.ItemId, Items.ItemName, Items.LastUpdate, Items.PreviewUrl, Subscriptions.Note FROM Subscriptions INNER JOIN Items ON Subscriptions.ItemId = Items.ItemId WHERE Subscriptions.ServerId = {} AND Subscriptions.LastUpdate > {}",
+    let res: Vec<(u64, String, u64, Option<String>, Option<u64>, Option<String>)> = conn.query(format!(
+        "SELECT Items.ItemId, Items.ItemName, Items.LastUpdate, Items.PreviewUrl, Items.FileSize, Subscriptions.Note FROM Subscriptions INNER JOIN Items ON Subscriptions.ItemId = Items.ItemId WHERE Subscriptions.ServerId = {} AND Subscriptions.RemovedAt IS NULL AND Subscriptions.LastUpdate > {} AND Subscriptions.LastUpdate <= {} ORDER BY Items.LastUpdate DESC",
         guild_id,
-        since
+        since,
+        until
     ))?;
 
     Ok(res
         .into_iter()
-        .map(|(id, name, last_updated, preview_url, note)| {
+        .map(|(id, name, last_updated, preview_url, file_size, note)| {
             (
                 ItemInfo {
                     id,
                     name,
                     last_updated,
                     preview_url,
+                    file_size,
                 },
                 note,
             )
         })
         .collect())
 }
+
+/// Records the latest known version of an item, or of all items if none is given, as deployed
+pub fn mark_deployed(pool: &Pool, guild_id: u64, item_id: Option<u64>) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    match item_id {
+        Some(item_id) => conn.exec_drop(
+            r"UPDATE Subscriptions INNER JOIN Items ON Subscriptions.ItemId = Items.ItemId SET Subscriptions.DeployedVersion = Items.LastUpdate WHERE Subscriptions.ServerId = :guild_id AND Subscriptions.ItemId = :item_id;",
+            params! {
+                "guild_id" => guild_id,
+                "item_id" => item_id,
+            },
+        )?,
+        None => conn.exec_drop(
+            r"UPDATE Subscriptions INNER JOIN Items ON Subscriptions.ItemId = Items.ItemId SET Subscriptions.DeployedVersion = Items.LastUpdate WHERE Subscriptions.ServerId = :guild_id;",
+            params! {
+                "guild_id" => guild_id,
+            },
+        )?,
+    }
+    Ok(())
+}
+
+/// Returns the deployed version of every item of a guild that was marked as deployed
+pub fn get_deployed_versions(pool: &Pool, guild_id: u64) -> Result<HashMap<u64, u64>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, u64)> = conn.query(format!(
+        "SELECT ItemId, DeployedVersion FROM Subscriptions WHERE ServerId = {} AND RemovedAt IS NULL AND DeployedVersion IS NOT NULL",
+        guild_id
+    ))?;
+
+    Ok(res.into_iter().collect())
+}
+
+/// Returns every item whose latest known version is newer than the deployed one, with the deployed version
+#[allow(clippy::type_complexity)]
+pub fn get_undeployed(pool: &Pool, guild_id: u64) -> Result<Vec<(ItemInfo, Option<u64>)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, String, u64, Option<String>, Option<u64>, Option<u64>)> = conn.query(format!(
+        "SELECT Items.ItemId, Items.ItemName, Items.LastUpdate, Items.PreviewUrl, Items.FileSize, Subscriptions.DeployedVersion FROM Subscriptions INNER JOIN Items ON Subscriptions.ItemId = Items.ItemId WHERE Subscriptions.ServerId = {} AND Subscriptions.RemovedAt IS NULL AND (Subscriptions.DeployedVersion IS NULL OR Subscriptions.DeployedVersion < Items.LastUpdate) ORDER BY Items.LastUpdate DESC",
+        guild_id
+    ))?;
+
+    Ok(res
+        .into_iter()
+        .map(
+            |(id, name, last_updated, preview_url, file_size, deployed)| {
+                (
+                    ItemInfo {
+                        id,
+                        name,
+                        last_updated,
+                        preview_url,
+                        file_size,
+                    },
+                    deployed,
+                )
+            },
+        )
+        .collect())
+}
+
+/// Mutes an item until the given timestamp, or until it is unmuted if none is given
+pub fn mute_item(
+    pool: &Pool,
+    guild_id: u64,
+    item_id: u64,
+    until: Option<u64>,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Subscriptions SET Muted = TRUE, MutedUntil = :until WHERE ServerId = :guild_id AND ItemId = :item_id;",
+        params! {
+            "until" => until,
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn unmute_item(pool: &Pool, guild_id: u64, item_id: u64) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Subscriptions SET Muted = FALSE, MutedUntil = NULL WHERE ServerId = :guild_id AND ItemId = :item_id;",
+        params! {
+            "guild_id" => guild_id,
+            "item_id" => item_id,
+        },
+    )?;
+    Ok(())
+}
+
+/// Returns every currently muted item of a guild and until when it is muted
+pub fn get_muted_items(pool: &Pool, guild_id: u64) -> Result<HashMap<u64, Option<u64>>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, Option<u64>)> = conn.query(format!(
+        "SELECT ItemId, MutedUntil FROM Subscriptions WHERE ServerId = {} AND RemovedAt IS NULL AND Muted = TRUE AND (MutedUntil IS NULL OR MutedUntil > UNIX_TIMESTAMP())",
+        guild_id
+    ))?;
+
+    Ok(res.into_iter().collect())
+}
+
+/// Returns who added every item of a guild and when, items added before this was recorded have none
+#[allow(clippy::type_complexity)]
+pub fn get_provenance(pool: &Pool, guild_id: u64) -> Result<HashMap<u64, Provenance>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(u64, Option<u64>, Option<u64>, Option<u64>)> = conn.query(format!(
+        "SELECT ItemId, AddedAt, AddedBy, AddedFrom FROM Subscriptions WHERE ServerId = {} AND RemovedAt IS NULL",
+        guild_id
+    ))?;
+
+    Ok(res
+        .into_iter()
+        .map(|(item_id, added_at, added_by, collection)| {
+            (
+                item_id,
+                Provenance {
+                    added_at,
+                    added_by,
+                    collection,
+                    added_by_name: None,
+                },
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_pool;
+
+    #[test]
+    #[ignore = "needs a MySQL database in TEST_MYSQL_URL"]
+    fn undo_restores_the_latest_removal_together() {
+        let (pool, guild_id) = test_pool();
+        let items = [guild_id, guild_id + 1, guild_id + 2];
+
+        let mut conn = pool.get_conn().unwrap();
+        for item_id in items {
+            conn.query_drop(format!(
+                "INSERT IGNORE INTO Items (ItemId, ItemName, LastUpdate) VALUES ({}, 'Test', 0);",
+                item_id
+            ))
+            .unwrap();
+            add_subscription(&pool, guild_id, item_id, 1, None).unwrap();
+        }
+
+        remove_subscriptions(&pool, guild_id, &items[..2]).unwrap();
+        // Batches are told apart by the millisecond they were removed in
+        std::thread::sleep(time::Duration::from_millis(5));
+        remove_subscription(&pool, guild_id, items[2]).unwrap();
+
+        assert_eq!(get_trash(&pool, guild_id).unwrap().len(), 3);
+        assert_eq!(count_guild_subscriptions(&pool, guild_id).unwrap(), 0);
+
+        assert_eq!(restore_subscriptions(&pool, guild_id, None).unwrap(), 1);
+        assert_eq!(restore_subscriptions(&pool, guild_id, None).unwrap(), 2);
+        assert_eq!(restore_subscriptions(&pool, guild_id, None).unwrap(), 0);
+        assert_eq!(count_guild_subscriptions(&pool, guild_id).unwrap(), 3);
+    }
+}
@@ -0,0 +1,121 @@
+use mysql::{params, prelude::Queryable, Pool};
+
+use crate::{i18n::Language, Error};
+
+/// Adds a watch, and re-enables DMs for the user in case they were closed before.
+/// The DMs are written in the language of the guild the user last added a watch in.
+pub fn add_watch(pool: &Pool, user_id: u64, item_id: u64, language: Language) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"INSERT INTO Watchers (UserId, Language) VALUES (:user_id, :language) ON DUPLICATE KEY UPDATE DmClosed = FALSE, Language = :language;",
+        params! {
+            "user_id" => user_id,
+            "language" => language.as_str(),
+        },
+    )?;
+
+    conn.exec_drop(
+        r"INSERT IGNORE INTO Watches (UserId, ItemId, LastNotified) SELECT :user_id, ItemId, LastUpdate FROM Items WHERE ItemId = :item_id;",
+        params! {
+            "user_id" => user_id,
+            "item_id" => item_id,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn remove_watch(pool: &Pool, user_id: u64, item_id: u64) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"DELETE FROM Watches WHERE UserId = :user_id AND ItemId = :item_id;",
+        params! {
+            "user_id" => user_id,
+            "item_id" => item_id,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn get_watched_items(pool: &Pool, user_id: u64) -> Result<Vec<(String, u64)>, Error> {
+    let mut conn = pool.get_conn()?;
+
+    let res: Vec<(String, u64)> = conn.query(format!(
+        "SELECT Items.ItemName, Items.ItemId FROM Watches INNER JOIN Items ON Watches.ItemId = Items.ItemId WHERE Watches.UserId = {} ORDER BY Items.ItemName",
+        user_id
+    ))?;
+
+    Ok(res)
+}
+
+/// Returns the users, in the language of their DMs, and the items they have not been told about yet.
+/// Users with closed DMs are skipped.
+#[allow(clippy::type_complexity)]
+pub fn get_watchers_to_notify(
+    pool: &Pool,
+    items: &[(u64, u64)],
+) -> Result<Vec<((u64, Language), u64)>, Error> {
+    if items.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut conn = pool.get_conn()?;
+
+    let condition = items
+        .iter()
+        .map(|(item_id, last_updated)| {
+            format!(
+                "(Watches.ItemId = {} AND Watches.LastNotified < {})",
+                item_id, last_updated
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" OR ");
+
+    let res: Vec<(u64, u64, Option<String>)> = conn.query(format!(
+        "SELECT Watches.UserId, Watches.ItemId, Watchers.Language FROM Watches INNER JOIN Watchers ON Watches.UserId = Watchers.UserId WHERE Watchers.DmClosed = FALSE AND ({})",
+        condition
+    ))?;
+
+    res.into_iter()
+        .map(|(user_id, item_id, language)| {
+            let language = match language {
+                Some(language) => Language::from_db(&language)?,
+                None => Language::English,
+            };
+            Ok(((user_id, language), item_id))
+        })
+        .collect()
+}
+
+pub fn update_last_notified(
+    pool: &Pool,
+    user_id: u64,
+    item_id: u64,
+    last_updated: u64,
+) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Watches SET LastNotified = :last_updated WHERE UserId = :user_id AND ItemId = :item_id;",
+        params! {
+            "last_updated" => last_updated,
+            "user_id" => user_id,
+            "item_id" => item_id,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn set_dm_closed(pool: &Pool, user_id: u64) -> Result<(), Error> {
+    let mut conn = pool.get_conn()?;
+
+    conn.exec_drop(
+        r"UPDATE Watchers SET DmClosed = TRUE WHERE UserId = :user_id;",
+        params! {
+            "user_id" => user_id,
+        },
+    )?;
+    Ok(())
+}
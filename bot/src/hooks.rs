@@ -0,0 +1,301 @@
+use std::{net::IpAddr, time::Duration};
+
+use hmac::{Hmac, Mac};
+use mysql::Pool;
+use sha2::Sha256;
+use tokio::{net::lookup_host, time::sleep};
+use tracing::{debug, warn};
+
+use crate::{
+    db::{self, ItemInfo},
+    Error,
+};
+
+/// How often a delivery is attempted before we give up
+const MAX_ATTEMPTS: u32 = 5;
+
+/// What an event sent to a hook is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    ItemUpdated,
+    /// Sent by /test_hook, receivers should not act on it
+    Test,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::ItemUpdated => "item_updated",
+            EventKind::Test => "test",
+        }
+    }
+}
+
+/// An update that is sent to the outbound hook of a guild
+pub struct UpdateEvent {
+    pub item_info: ItemInfo,
+    pub previous_update: u64,
+    pub note: Option<String>,
+}
+
+/// Sends every update to the outbound hook of the guild, if one is configured.
+/// Deliveries run in the background, so a slow receiver can't hold up the tracking job.
+pub fn send_update_events(
+    pool: &Pool,
+    guild_id: u64,
+    events: Vec<UpdateEvent>,
+) -> Result<(), Error> {
+    let (url, secret) = match db::servers::get_hook(pool, guild_id)? {
+        Some(hook) => hook,
+        None => return Ok(()),
+    };
+
+    for event in events {
+        let payload = to_payload(guild_id, EventKind::ItemUpdated, &event);
+        let url = url.clone();
+        let secret = secret.clone();
+
+        tokio::spawn(async move {
+            // The url may have been set before only public hosts were allowed
+            if !is_allowed_url(&url).await {
+                warn!(
+                    "Not delivering update of item {} to the hook of guild {}, the url is not allowed",
+                    event.item_info.id, guild_id
+                );
+                return;
+            }
+
+            if let Err(e) = deliver(&url, &secret, &payload).await {
+                warn!(
+                    "Failed to deliver update of item {} to the hook of guild {}: {}",
+                    event.item_info.id, guild_id, e
+                );
+            }
+        });
+    }
+
+    Ok(())
+}
+
+pub fn to_payload(guild_id: u64, kind: EventKind, event: &UpdateEvent) -> String {
+    json::object! {
+        event: kind.as_str(),
+        // Discord ids don't fit into a javascript number, so we send them as strings
+        guild_id: guild_id.to_string(),
+        item_id: event.item_info.id,
+        name: event.item_info.name.as_str(),
+        url: format!(
+            "https://steamcommunity.com/sharedfiles/filedetails/?id={}",
+            event.item_info.id
+        ),
+        previous_time_updated: event.previous_update,
+        time_updated: event.item_info.last_updated,
+        file_size: event.item_info.file_size,
+        note: event.note.as_deref(),
+    }
+    .dump()
+}
+
+/// Only http and https urls of public hosts may be used,
+/// so managers can't make the bot send requests into the network it runs in
+pub async fn is_allowed_url(url: &str) -> bool {
+    let url = match reqwest::Url::parse(url) {
+        Ok(url) => url,
+        Err(_) => return false,
+    };
+
+    if !matches!(url.scheme(), "https" | "http") {
+        return false;
+    }
+
+    let (host, port) = match (url.host_str(), url.port_or_known_default()) {
+        (Some(host), Some(port)) => (host, port),
+        _ => return false,
+    };
+
+    // Names are resolved, so they can't hide a private address
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    match lookup_host((host, port)).await {
+        Ok(addresses) => {
+            let addresses: Vec<_> = addresses.collect();
+            !addresses.is_empty() && addresses.iter().all(|a| is_public(a.ip()))
+        }
+        Err(_) => false,
+    }
+}
+
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast())
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// Posts the payload to the url, retrying with an exponential backoff.
+/// The hex encoded HMAC-SHA256 of the body is sent in the `X-Signature-256` header.
+pub async fn deliver(url: &str, secret: &str, payload: &str) -> Result<(), Error> {
+    // A redirect could lead to a host that is not allowed
+    let c = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()?;
+    let signature = sign(secret, payload)?;
+
+    let mut attempt = 1;
+
+    loop {
+        let res = c
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("X-Signature-256", format!("sha256={}", signature))
+            .body(payload.to_string())
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status());
+
+        match res {
+            Ok(_) => {
+                debug!("Delivered event to {} after {} attempt(s)", url, attempt);
+                return Ok(());
+            }
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                debug!("Delivery attempt {} to {} failed: {}", attempt, url, e);
+                sleep(Duration::from_secs(2u64.pow(attempt - 1))).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+fn sign(secret: &str, payload: &str) -> Result<String, Error> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())?;
+    mac.update(payload.as_bytes());
+
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn only_public_http_urls_are_allowed() {
+        assert!(is_allowed_url("https://1.1.1.1/hook").await);
+        assert!(!is_allowed_url("ftp://1.1.1.1/hook").await);
+        assert!(!is_allowed_url("not a url").await);
+
+        for url in [
+            "http://127.0.0.1/hook",
+            "http://localhost:8080/hook",
+            "http://10.0.0.1/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(!is_allowed_url(url).await, "{}", url);
+        }
+    }
+
+    #[test]
+    fn sign_matches_known_vector() {
+        // Test case 2 of RFC 4231
+        assert_eq!(
+            sign("Jefe", "what do ya want for nothing?").unwrap(),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    /// Reads one request and answers it with the status, returns the request
+    async fn answer(listener: &TcpListener, status: &str) -> String {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+
+            let text = String::from_utf8_lossy(&request);
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text
+                    .lines()
+                    .find_map(|l| {
+                        l.to_lowercase()
+                            .strip_prefix("content-length: ")
+                            .map(|v| v.parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if request.len() >= end + 4 + length || n == 0 {
+                    break;
+                }
+            }
+        }
+
+        stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+
+        String::from_utf8(request).unwrap()
+    }
+
+    #[tokio::test]
+    async fn deliver_retries_until_accepted() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+        let receiver = tokio::spawn(async move {
+            let first = answer(&listener, "500 Internal Server Error").await;
+            let second = answer(&listener, "200 OK").await;
+            (first, second)
+        });
+
+        deliver(&url, "secret", r#"{"event":"item_updated"}"#)
+            .await
+            .unwrap();
+
+        let (first, second) = receiver.await.unwrap();
+        let header = format!(
+            "x-signature-256: sha256={}",
+            sign("secret", r#"{"event":"item_updated"}"#).unwrap()
+        );
+
+        for request in [first, second] {
+            assert!(request.to_lowercase().contains(&header));
+            assert!(request.ends_with(r#"{"event":"item_updated"}"#));
+        }
+    }
+}
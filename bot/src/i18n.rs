@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use mysql::Pool;
+use tracing::error;
+
+use crate::Error;
+
+mod de;
+
+lazy_static! {
+    static ref GERMAN: HashMap<&'static str, &'static str> = de::MESSAGES.iter().copied().collect();
+    static ref LANGUAGES: DashMap<u64, Language> = DashMap::new();
+}
+
+/// The language the bot answers a guild in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, poise::ChoiceParameter)]
+pub enum Language {
+    #[default]
+    #[name = "English"]
+    English,
+    #[name = "Deutsch"]
+    German,
+}
+
+impl Language {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    pub fn from_db(s: &str) -> Result<Self, Error> {
+        match s {
+            "en" => Ok(Language::English),
+            "de" => Ok(Language::German),
+            _ => Err(format!("Unknown language: {}", s).into()),
+        }
+    }
+}
+
+/// Looks up the translation of an english message.
+/// Messages missing from a catalog are returned as they are.
+pub fn translate(language: Language, msg: &str) -> &str {
+    match language {
+        Language::English => msg,
+        Language::German => GERMAN.get(msg).copied().unwrap_or(msg),
+    }
+}
+
+/// The language of a guild, cached so replies don't hit the database every time
+pub fn guild_language(pool: &Pool, guild_id: Option<u64>) -> Language {
+    let guild_id = match guild_id {
+        Some(id) => id,
+        None => return Language::English,
+    };
+
+    if let Some(language) = LANGUAGES.get(&guild_id) {
+        return *language;
+    }
+
+    match crate::db::servers::get_language(pool, guild_id) {
+        Ok(language) => {
+            LANGUAGES.insert(guild_id, language);
+            language
+        }
+        Err(e) => {
+            error!("Error getting language of guild {}: {:?}", guild_id, e);
+            Language::English
+        }
+    }
+}
+
+pub fn set_guild_language(pool: &Pool, guild_id: u64, language: Language) -> Result<(), Error> {
+    crate::db::servers::set_language(pool, guild_id, language)?;
+    LANGUAGES.insert(guild_id, language);
+    Ok(())
+}
+
+/// Translates a message into the language of the guild of a command.
+/// Placeholders like `{name}` are filled with the named arguments.
+macro_rules! tr {
+    ($ctx:expr, $msg:expr) => {
+        crate::i18n::translate(
+            crate::i18n::guild_language(&$ctx.data().pool, $ctx.guild_id().map(|g| g.0)),
+            &$msg,
+        )
+        .to_string()
+    };
+    ($ctx:expr, $msg:expr, $($key:ident = $value:expr),+ $(,)?) => {
+        crate::template::render(
+            crate::i18n::translate(
+                crate::i18n::guild_language(&$ctx.data().pool, $ctx.guild_id().map(|g| g.0)),
+                $msg,
+            ),
+            &[$((stringify!($key), $value.to_string())),+],
+        )
+    };
+}
+
+pub(crate) use tr;
@@ -0,0 +1,311 @@
+/// German translations, keyed by the english message
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Shared
+    ("This command can only be used in a guild.", "Dieser Befehl kann nur auf einem Server verwendet werden."),
+    ("Please set an update channel first, or run /setup.", "Bitte lege zuerst einen Update-Kanal fest oder führe /setup aus."),
+    ("Please set an update channel and a schedule first, or run /setup.", "Bitte lege zuerst einen Update-Kanal und einen Zeitplan fest oder führe /setup aus."),
+    ("The update channel is no longer available", "Der Update-Kanal ist nicht mehr verfügbar"),
+    ("Could not reach the update channel. Please set an update channel first.", "Der Update-Kanal ist nicht erreichbar. Bitte lege zuerst einen Update-Kanal fest."),
+    ("There is no group called {name}.", "Es gibt keine Gruppe namens {name}."),
+    ("Could not find the item.", "Der Eintrag wurde nicht gefunden."),
+    ("Could not find the item by that name.", "Es wurde kein Eintrag mit diesem Namen gefunden."),
+    ("This item is not tracked.", "Dieser Eintrag wird nicht überwacht."),
+    ("This item is not tracked by this server.", "Dieser Eintrag wird von diesem Server nicht überwacht."),
+    ("There are no tracked items.", "Es werden keine Einträge überwacht."),
+    ("Success", "Erledigt"),
+    ("Yes", "Ja"),
+    ("No", "Nein"),
+    ("Prev", "Zurück"),
+    ("Next", "Weiter"),
+    ("Cancel", "Abbrechen"),
+    ("Cancelled.", "Abgebrochen."),
+    ("Processing... Please wait.", "Wird bearbeitet... Bitte warten."),
+    (":warning: You didn't interact in time - please run the command again.", ":warning: Du hast nicht rechtzeitig reagiert - bitte führe den Befehl erneut aus."),
+    ("Error: {error}", "Fehler: {error}"),
+    ("Part {part}/{parts}", "Teil {part}/{parts}"),
+    // Errors
+    ("An error occurred while adding the item.", "Beim Hinzufügen des Eintrags ist ein Fehler aufgetreten."),
+    ("An error occurred while adding the item to the group.", "Beim Hinzufügen des Eintrags zur Gruppe ist ein Fehler aufgetreten."),
+    ("An error occurred while adding the item to your watchlist.", "Beim Hinzufügen des Eintrags zu deiner Beobachtungsliste ist ein Fehler aufgetreten."),
+    ("An error occurred while adding the server to the database.", "Beim Speichern des Servers ist ein Fehler aufgetreten."),
+    ("An error occurred while checking for updates.", "Bei der Suche nach Updates ist ein Fehler aufgetreten."),
+    ("An error occurred while checking if the bot is still in the guild.", "Bei der Prüfung, ob der Bot noch auf dem Server ist, ist ein Fehler aufgetreten."),
+    ("An error occurred while checking the group.", "Beim Prüfen der Gruppe ist ein Fehler aufgetreten."),
+    ("An error occurred while checking the item.", "Beim Prüfen des Eintrags ist ein Fehler aufgetreten."),
+    ("An error occurred while creating the group.", "Beim Erstellen der Gruppe ist ein Fehler aufgetreten."),
+    ("An error occurred while deleting the group.", "Beim Löschen der Gruppe ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the changes.", "Beim Laden der Änderungen ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the check intervals.", "Beim Laden der Prüfintervalle ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the collection.", "Beim Laden der Sammlung ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the group.", "Beim Laden der Gruppe ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the groups.", "Beim Laden der Gruppen ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the hook.", "Beim Laden des Hooks ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the item.", "Beim Laden des Eintrags ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the item {id}.", "Beim Laden des Eintrags {id} ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the items.", "Beim Laden der Einträge ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the last restart.", "Beim Laden des letzten Neustarts ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the last update.", "Beim Laden des letzten Updates ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the muted items.", "Beim Laden der stummgeschalteten Einträge ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the notification mode.", "Beim Laden des Benachrichtigungsmodus ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the pending updates.", "Beim Laden der offenen Updates ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the schedule.", "Beim Laden des Zeitplans ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the subscription.", "Beim Laden des Abonnements ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the subscriptions.", "Beim Laden der Abonnements ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the templates.", "Beim Laden der Vorlagen ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the timezone.", "Beim Laden der Zeitzone ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the trash.", "Beim Laden des Papierkorbs ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the update channel.", "Beim Laden des Update-Kanals ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching your watchlist.", "Beim Laden deiner Beobachtungsliste ist ein Fehler aufgetreten."),
+    ("An error occurred while marking the item as deployed.", "Beim Markieren des Eintrags als installiert ist ein Fehler aufgetreten."),
+    ("An error occurred while muting the item.", "Beim Stummschalten des Eintrags ist ein Fehler aufgetreten."),
+    ("An error occurred while parsing the item ids.", "Beim Lesen der IDs ist ein Fehler aufgetreten."),
+    ("An error occurred while posting the collected updates.", "Beim Posten der gesammelten Updates ist ein Fehler aufgetreten."),
+    ("An error occurred while removing all items.", "Beim Entfernen aller Einträge ist ein Fehler aufgetreten."),
+    ("An error occurred while removing the hook.", "Beim Entfernen des Hooks ist ein Fehler aufgetreten."),
+    ("An error occurred while removing the item.", "Beim Entfernen des Eintrags ist ein Fehler aufgetreten."),
+    ("An error occurred while removing the item from the group.", "Beim Entfernen des Eintrags aus der Gruppe ist ein Fehler aufgetreten."),
+    ("An error occurred while removing the item from your watchlist.", "Beim Entfernen des Eintrags von deiner Beobachtungsliste ist ein Fehler aufgetreten."),
+    ("An error occurred while removing the items.", "Beim Entfernen der Einträge ist ein Fehler aufgetreten."),
+    ("An error occurred while removing the webhook.", "Beim Entfernen des Webhooks ist ein Fehler aufgetreten."),
+    ("An error occurred while restarting the tracking job.", "Beim Neustarten der Überwachung ist ein Fehler aufgetreten."),
+    ("An error occurred while restoring the items.", "Beim Wiederherstellen der Einträge ist ein Fehler aufgetreten."),
+    ("An error occurred while subscribing to the item {name}.", "Beim Abonnieren des Eintrags {name} ist ein Fehler aufgetreten."),
+    ("An error occurred while unmuting the item.", "Beim Aufheben der Stummschaltung ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the channel.", "Beim Aktualisieren des Kanals ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the check interval.", "Beim Aktualisieren des Prüfintervalls ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the escalation rule.", "Beim Aktualisieren der Eskalation ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the hook.", "Beim Aktualisieren des Hooks ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the language.", "Beim Aktualisieren der Sprache ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the mention.", "Beim Aktualisieren der Erwähnung ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the note.", "Beim Aktualisieren der Notiz ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the notification mode.", "Beim Aktualisieren des Benachrichtigungsmodus ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the schedule.", "Beim Aktualisieren des Zeitplans ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the template.", "Beim Aktualisieren der Vorlage ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the timezone.", "Beim Aktualisieren der Zeitzone ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the webhook.", "Beim Aktualisieren des Webhooks ist ein Fehler aufgetreten."),
+    // Adding and removing
+    ("Item already tracked.", "Der Eintrag wird bereits überwacht."),
+    ("Added item {name} to the tracked items:", "{name} wird jetzt überwacht:"),
+    ("Got collection. Adding {count} items", "Sammlung geladen. {count} Einträge werden hinzugefügt"),
+    ("Removed item {name} from the tracked items:", "{name} wird nicht mehr überwacht:"),
+    ("Success. Use /undo_remove to restore it.", "Erledigt. Mit /undo_remove kannst du ihn wiederherstellen."),
+    ("Done! Use /undo_remove to restore them.", "Erledigt! Mit /undo_remove kannst du sie wiederherstellen."),
+    ("Removed all items.", "Alle Einträge wurden entfernt."),
+    ("None of the {count} items in the collection are tracked.", "Keiner der {count} Einträge der Sammlung wird überwacht."),
+    ("{count} of the {total} items in the collection are tracked: {names}", "{count} der {total} Einträge der Sammlung werden überwacht: {names}"),
+    ("Are you sure you want to remove {count} items?", "Willst du wirklich {count} Einträge entfernen?"),
+    ("Removed {count} items of collection {collection}: {names}", "{count} Einträge der Sammlung {collection} wurden entfernt: {names}"),
+    ("Selected {count} items.", "{count} Einträge ausgewählt."),
+    ("Removed {count} items: {names}", "{count} Einträge wurden entfernt: {names}"),
+    ("Select the items to remove. {count} items selected.", "Wähle die Einträge aus, die entfernt werden sollen. {count} Einträge ausgewählt."),
+    ("Items (page {page} of {pages})", "Einträge (Seite {page} von {pages})"),
+    ("Remove selected", "Auswahl entfernen"),
+    ("The trash is empty.", "Der Papierkorb ist leer."),
+    ("Removed items, restore them with /undo_remove:", "Entfernte Einträge, mit /undo_remove wiederherstellbar:"),
+    ("There is nothing to restore. See /trash for the removed items.", "Es gibt nichts wiederherzustellen. Die entfernten Einträge findest du mit /trash."),
+    ("Restored 1 item.", "1 Eintrag wurde wiederhergestellt."),
+    ("Restored {count} items.", "{count} Einträge wurden wiederhergestellt."),
+    ("Exported {count} items.", "{count} Einträge exportiert."),
+    // Checking
+    ("Please wait {seconds} more seconds before checking again.", "Bitte warte noch {seconds} Sekunden, bevor du erneut prüfst."),
+    ("Checked {checked} items, {updated} updated, {failed} failed.", "{checked} Einträge geprüft, {updated} aktualisiert, {failed} fehlgeschlagen."),
+    ("Please set a schedule first.", "Bitte lege zuerst einen Zeitplan fest."),
+    ("Restarted tracking job.", "Die Überwachung wurde neu gestartet."),
+    // Deployed versions
+    ("Please provide an item, or set all to mark everything as deployed.", "Bitte gib einen Eintrag an oder setze all, um alles als installiert zu markieren."),
+    ("Marked the item as deployed.", "Der Eintrag wurde als installiert markiert."),
+    ("Marked all items as deployed.", "Alle Einträge wurden als installiert markiert."),
+    ("Your server is up to date.", "Dein Server ist auf dem neuesten Stand."),
+    ("Your server is behind on {count} items:", "Bei {count} Einträgen ist dein Server nicht aktuell:"),
+    // Groups
+    ("A group called {name} already exists.", "Es gibt bereits eine Gruppe namens {name}."),
+    ("Created group {name}.", "Die Gruppe {name} wurde erstellt."),
+    ("Deleted group {name}.", "Die Gruppe {name} wurde gelöscht."),
+    ("Added {name} to {group}.", "{name} wurde zu {group} hinzugefügt."),
+    ("Removed {name} from {group}.", "{name} wurde aus {group} entfernt."),
+    ("There are no groups. Create one with /create_group.", "Es gibt keine Gruppen. Erstelle eine mit /create_group."),
+    ("Groups:", "Gruppen:"),
+    // List
+    ("Currently tracked items", "Aktuell überwachte Einträge"),
+    ("Page {page} of {pages} - {count} items", "Seite {page} von {pages} - {count} Einträge"),
+    ("updated <t:{time}:R>", "aktualisiert <t:{time}:R>"),
+    (", every {interval}", ", alle {interval}"),
+    (", not checked", ", wird nicht geprüft"),
+    (" :mute: until <t:{until}:R>", " :mute: bis <t:{until}:R>"),
+    ("Sort by name", "Nach Name sortieren"),
+    ("Sort by last updated", "Nach letztem Update sortieren"),
+    ("Sort by date added", "Nach Hinzufügedatum sortieren"),
+    // Mute
+    ("The date has to be in the future.", "Das Datum muss in der Zukunft liegen."),
+    ("Invalid duration. Use something like 3d, 12h, 2 weeks or a date. Dates are in {timezone}.", "Ungültige Dauer. Verwende etwas wie 3d, 12h, 2 weeks oder ein Datum. Daten sind in {timezone}."),
+    ("Muted {name} until <t:{until}:f>.", "{name} ist bis <t:{until}:f> stummgeschaltet."),
+    ("Muted {name} until you /unmute it.", "{name} ist stummgeschaltet, bis du /unmute verwendest."),
+    ("Unmuted {name}.", "Die Stummschaltung von {name} wurde aufgehoben."),
+    // Pending updates
+    ("All updates have been handled.", "Alle Updates wurden erledigt."),
+    ("Pending updates:", "Offene Updates:"),
+    ("You don't have permission to handle updates.", "Du hast keine Berechtigung, Updates zu erledigen."),
+    (":white_check_mark: {name} was handled by <@{user}>", ":white_check_mark: {name} wurde von <@{user}> erledigt"),
+    (":zzz: {name} was snoozed by <@{user}> until <t:{until}:f>", ":zzz: {name} wurde von <@{user}> bis <t:{until}:f> zurückgestellt"),
+    ("{name} handled by {user}", "{name} erledigt von {user}"),
+    ("Snoozed by {user}", "Zurückgestellt von {user}"),
+    // Changes since
+    ("Invalid time. Use something like 3d, 2 weeks, last restart or a date like mm/dd/yy. Dates are in {timezone}.", "Ungültige Zeit. Verwende etwas wie 3d, 2 weeks, last restart oder ein Datum wie mm/dd/yy. Daten sind in {timezone}."),
+    ("Whoa there bucko! I can't track the future! Be aware that all my dates are in {timezone}!", "Moment mal! Ich kann nicht in die Zukunft schauen! Denk daran, dass alle Daten in {timezone} sind!"),
+    ("The start has to be before the end.", "Der Beginn muss vor dem Ende liegen."),
+    ("No changes in that time.", "Keine Änderungen in diesem Zeitraum."),
+    ("Changes between <t:{since}:f> and <t:{until}:f>:", "Änderungen zwischen <t:{since}:f> und <t:{until}:f>:"),
+    ("Sending", "Wird gesendet"),
+    // Watchlist
+    ("Added {name} to your watchlist. Make sure you allow DMs from this server.", "{name} ist jetzt auf deiner Beobachtungsliste. Stelle sicher, dass du DMs von diesem Server erlaubst."),
+    ("Removed {name} from your watchlist.", "{name} wurde von deiner Beobachtungsliste entfernt."),
+    ("Your watchlist is empty.", "Deine Beobachtungsliste ist leer."),
+    // Info
+    ("Your server is subscribed to {count} mods\n", "Dein Server überwacht {count} Mods\n"),
+    ("running", "aktiv"),
+    ("not running", "nicht aktiv"),
+    ("never", "nie"),
+    ("The tracking job is {status}\n", "Die Überwachung ist {status}\n"),
+    ("The last update was: {time}\n", "Das letzte Update war: {time}\n"),
+    ("Updates are posted: {mode}\n", "Updates werden gepostet: {mode}\n"),
+    ("Dates are read in: {timezone}\n", "Daten werden gelesen in: {timezone}\n"),
+    // Settings
+    ("Please provide a valid channel id.", "Bitte gib eine gültige Kanal-ID an."),
+    ("Update channel set.", "Der Update-Kanal wurde festgelegt."),
+    ("{name} will be checked every {minutes} minutes.", "{name} wird alle {minutes} Minuten geprüft."),
+    ("{name} will be checked on the server schedule.", "{name} wird nach dem Zeitplan des Servers geprüft."),
+    ("Please provide both the hours and the role.", "Bitte gib sowohl die Stunden als auch die Rolle an."),
+    ("Updates of items in {group} not handled within {hours} hours will be escalated to <@&{role}>.", "Updates von Einträgen in {group}, die nicht innerhalb von {hours} Stunden erledigt werden, werden an <@&{role}> eskaliert."),
+    ("Updates not handled within {hours} hours will be escalated to <@&{role}>.", "Updates, die nicht innerhalb von {hours} Stunden erledigt werden, werden an <@&{role}> eskaliert."),
+    ("Escalation disabled.", "Die Eskalation wurde deaktiviert."),
+    ("Hook removed.", "Der Hook wurde entfernt."),
+    ("Please provide an http or https url of a public host.", "Bitte gib eine http- oder https-URL eines öffentlichen Hosts an."),
+    ("The hook url is not allowed, please set it again with /set_hook.", "Die URL des Hooks ist nicht erlaubt, bitte lege sie mit /set_hook neu fest."),
+    ("Please provide a secret to sign the events with.", "Bitte gib ein Geheimnis an, mit dem die Events signiert werden."),
+    ("Hook set. Use /test_hook to send a test event.", "Der Hook wurde festgelegt. Mit /test_hook kannst du ein Test-Event senden."),
+    ("Please set a hook first.", "Bitte lege zuerst einen Hook fest."),
+    ("Test event delivered.", "Das Test-Event wurde zugestellt."),
+    ("Delivery failed.", "Die Zustellung ist fehlgeschlagen."),
+    ("The bot now answers in English.", "Der Bot antwortet jetzt auf Deutsch."),
+    ("Please provide either an item or a group.", "Bitte gib entweder einen Eintrag oder eine Gruppe an."),
+    ("Mention removed.", "Die Erwähnung wurde entfernt."),
+    ("Mention set.", "Die Erwähnung wurde festgelegt."),
+    ("Updates will be posted immediately.", "Updates werden sofort gepostet."),
+    ("Updates will be posted every day at {hour}:00 {timezone}.", "Updates werden jeden Tag um {hour}:00 {timezone} gepostet."),
+    ("Updates will be posted every {day} at {hour}:00 {timezone}.", "Updates werden jeden {day} um {hour}:00 {timezone} gepostet."),
+    ("Schedule set.", "Der Zeitplan wurde festgelegt."),
+    ("Invalid template: {error}", "Ungültige Vorlage: {error}"),
+    ("Template set. Use /preview_template to see how it looks.", "Die Vorlage wurde festgelegt. Mit /preview_template siehst du, wie sie aussieht."),
+    ("Template reset to the default.", "Die Vorlage wurde auf den Standard zurückgesetzt."),
+    ("Unknown timezone. Use a name like Europe/Berlin or America/New_York.", "Unbekannte Zeitzone. Verwende einen Namen wie Europe/Berlin oder America/New_York."),
+    ("Dates are now read in {timezone}.", "Daten werden jetzt in {timezone} gelesen."),
+    ("Webhook removed. Updates will be posted by the bot.", "Der Webhook wurde entfernt. Updates werden vom Bot gepostet."),
+    ("Webhook set.", "Der Webhook wurde festgelegt."),
+    ("Please provide a valid webhook url.", "Bitte gib eine gültige Webhook-URL an."),
+    ("Could not create a webhook.", "Der Webhook konnte nicht erstellt werden."),
+    ("Could not create a webhook. Please make sure I have the Manage Webhooks permission.", "Der Webhook konnte nicht erstellt werden. Bitte stelle sicher, dass ich die Berechtigung Webhooks verwalten habe."),
+    ("Immediately", "Sofort"),
+    ("Daily digest", "Tägliche Übersicht"),
+    ("Weekly digest", "Wöchentliche Übersicht"),
+    ("Monday", "Montag"),
+    ("Tuesday", "Dienstag"),
+    ("Wednesday", "Mittwoch"),
+    ("Thursday", "Donnerstag"),
+    ("Friday", "Freitag"),
+    ("Saturday", "Samstag"),
+    ("Sunday", "Sonntag"),
+    // Permissions
+    ("You don't have permission to use this command.", "Du hast keine Berechtigung für diesen Befehl."),
+    ("An error occurred while checking your permissions.", "Beim Prüfen deiner Berechtigungen ist ein Fehler aufgetreten."),
+    ("An error occurred while fetching the permissions.", "Beim Laden der Berechtigungen ist ein Fehler aufgetreten."),
+    ("An error occurred while updating the permissions.", "Beim Aktualisieren der Berechtigungen ist ein Fehler aufgetreten."),
+    ("everyone", "alle"),
+    ("Managers: {manager}\nContributors: {contributor}\nMembers who can manage the server can always use every command.", "Verwalter: {manager}\nMitwirkende: {contributor}\nMitglieder, die den Server verwalten dürfen, können immer alle Befehle verwenden."),
+    ("{role} is now the {level} role.", "{role} ist jetzt die Rolle für {level}."),
+    ("Everyone may now use the {level} commands.", "Jetzt dürfen alle die Befehle für {level} verwenden."),
+    ("Manager", "Verwalter"),
+    ("Contributor", "Mitwirkende"),
+    // Audit log
+    ("An error occurred while fetching the audit log.", "Beim Laden des Protokolls ist ein Fehler aufgetreten."),
+    ("No changes have been recorded.", "Es wurden keine Änderungen aufgezeichnet."),
+    ("Latest changes:", "Letzte Änderungen:"),
+    ("added {item}", "{item} hinzugefügt"),
+    ("removed {item}", "{item} entfernt"),
+    ("{field} of {item}: {old} → {new}", "{field} von {item}: {old} → {new}"),
+    ("note", "Notiz"),
+    ("muted", "Stummgeschaltet"),
+    ("check interval", "Prüfintervall"),
+    ("deployed version", "Installierte Version"),
+    ("group", "Gruppe"),
+    ("group mention", "Gruppen-Erwähnung"),
+    ("mention", "Erwähnung"),
+    ("webhook", "Webhook"),
+    ("hook", "Hook"),
+    ("header template", "Kopfzeilen-Vorlage"),
+    ("item template", "Eintrags-Vorlage"),
+    ("timezone", "Zeitzone"),
+    ("language", "Sprache"),
+    ("notification mode", "Benachrichtigungsmodus"),
+    ("escalation", "Eskalation"),
+    ("channel", "Kanal"),
+    ("schedule", "Zeitplan"),
+    ("manager role", "Verwalter-Rolle"),
+    ("contributor role", "Mitwirkenden-Rolle"),
+    // Provenance
+    (", added by <@{user}> <t:{time}:R>", ", hinzugefügt von <@{user}> <t:{time}:R>"),
+    (", added by <@{user}>", ", hinzugefügt von <@{user}>"),
+    (", added <t:{time}:R>", ", hinzugefügt <t:{time}:R>"),
+    (" from collection {collection}", " aus Sammlung {collection}"),
+    ("Added by {user} from collection {collection}", "Hinzugefügt von {user} aus Sammlung {collection}"),
+    ("Added by {user}", "Hinzugefügt von {user}"),
+    ("Added from collection {collection}", "Hinzugefügt aus Sammlung {collection}"),
+    // Notes
+    ("An error occurred while fetching the notes.", "Beim Laden der Notizen ist ein Fehler aufgetreten."),
+    ("The note is empty.", "Die Notiz ist leer."),
+    ("Note removed.", "Notiz entfernt."),
+    ("{name} has no note #{note}. See /notes for its notes.", "{name} hat keine Notiz #{note}. Die Notizen findest du mit /notes."),
+    ("{name} has no notes. Add one with /add_note.", "{name} hat keine Notizen. Füge mit /add_note eine hinzu."),
+    ("(removed by <@{user}> <t:{time}:R>)", "(entfernt von <@{user}> <t:{time}:R>)"),
+    ("Notes of {name}:", "Notizen zu {name}:"),
+    // Setup
+    ("This server has no text channels to post updates in.", "Dieser Server hat keine Textkanäle, in die Updates gepostet werden können."),
+    ("**Step 1 of 3:** Which channel should updates be posted in?", "**Schritt 1 von 3:** In welchem Kanal sollen Updates gepostet werden?"),
+    (":warning: I can't post in {channel}, I am missing these permissions: {permissions}\n**Step 1 of 3:** Which channel should updates be posted in?", ":warning: Ich kann nicht in {channel} posten, mir fehlen diese Berechtigungen: {permissions}\n**Schritt 1 von 3:** In welchem Kanal sollen Updates gepostet werden?"),
+    ("**Step 2 of 3:** Which role should be pinged when items update?", "**Schritt 2 von 3:** Welche Rolle soll bei Updates gepingt werden?"),
+    ("**Step 3 of 3:** How often should I look for updates?", "**Schritt 3 von 3:** Wie oft soll ich nach Updates suchen?"),
+    (":white_check_mark: This is a test message. Updates of the tracked items will be posted here.", ":white_check_mark: Dies ist eine Testnachricht. Updates der überwachten Einträge werden hier gepostet."),
+    (":warning: I could not post in {channel}: {error}", ":warning: Ich konnte nicht in {channel} posten: {error}"),
+    ("All set! Updates will be posted in {channel}, I will look for them {schedule}. Add items with /add or /add_collection.", "Fertig! Updates werden in {channel} gepostet, ich suche {schedule} danach. Füge Einträge mit /add oder /add_collection hinzu."),
+    ("View Channel", "Kanal ansehen"),
+    ("Send Messages", "Nachrichten senden"),
+    ("Embed Links", "Links einbetten"),
+    ("Update channel", "Update-Kanal"),
+    ("Role to ping", "Zu pingende Rolle"),
+    ("Don't ping a role", "Keine Rolle pingen"),
+    ("Schedule", "Zeitplan"),
+    ("Every hour", "Jede Stunde"),
+    ("Every {hours} hours", "Alle {hours} Stunden"),
+    ("every hour", "jede Stunde"),
+    ("every {hours} hours", "alle {hours} Stunden"),
+    // Update messages
+    ("The following items were updated:", "Die folgenden Einträge wurden aktualisiert:"),
+    ("The following items were updated since <t:{since}:f>:", "Die folgenden Einträge wurden seit <t:{since}:f> aktualisiert:"),
+    ("The following Items could not be updated:", "Die folgenden Einträge konnten nicht geprüft werden:"),
+    ("The following updates were not handled within {hours} hours:", "Die folgenden Updates wurden nicht innerhalb von {hours} Stunden erledigt:"),
+    ("{name} (updated {count} times)", "{name} ({count} mal aktualisiert)"),
+    ("Handled: {name}", "Erledigt: {name}"),
+    ("Snooze 1 day", "1 Tag zurückstellen"),
+    ("Updated", "Aktualisiert"),
+    ("Groups", "Gruppen"),
+    ("Note: {note}", "Notiz: {note}"),
+    ("Notes:\n{note}", "Notizen:\n{note}"),
+    ("Items on your watchlist were updated:", "Einträge auf deiner Beobachtungsliste wurden aktualisiert:"),
+    ("{name}: {count} items", "{name}: {count} Einträge"),
+    ("never marked", "nie markiert"),
+    ("{name}: updated <t:{updated}:R>, deployed {deployed} <https://steamcommunity.com/sharedfiles/filedetails/?id={id}>", "{name}: aktualisiert <t:{updated}:R>, installiert {deployed} <https://steamcommunity.com/sharedfiles/filedetails/?id={id}>"),
+    ("Are you sure you want to remove all items?", "Willst du wirklich alle Einträge entfernen?"),
+    ("{name} ({id}): removed <t:{removed}:R>, deleted <t:{deleted}:R>", "{name} ({id}): entfernt <t:{removed}:R>, wird gelöscht <t:{deleted}:R>"),
+    ("{name}: updated <t:{updated}:R>, posted <t:{posted}:R> <https://steamcommunity.com/sharedfiles/filedetails/?id={id}>", "{name}: aktualisiert <t:{updated}:R>, gepostet <t:{posted}:R> <https://steamcommunity.com/sharedfiles/filedetails/?id={id}>"),
+    ("Your watchlist:", "Deine Beobachtungsliste:"),
+    ("{skipped} items were skipped, their last update was posted less than an hour ago.", "{skipped} Einträge wurden übersprungen, ihr letztes Update wurde vor weniger als einer Stunde gepostet."),
+];
@@ -4,7 +4,7 @@ use commands::actions::add_multiple::{collection_add, item_batch_add};
 use mysql::Pool;
 use poise::{
     builtins,
-    serenity_prelude::{self as serenity, Command},
+    serenity_prelude::{self as serenity, Command, Interaction},
     Event,
 };
 use tokio::time::sleep;
@@ -13,20 +13,39 @@ use tracing::{debug, error, info};
 use crate::commands::{
     actions::{
         add::item_add,
+        audit::audit,
+        check_now::check_now,
+        deployed::{mark_deployed, pending_updates},
+        export::export,
+        groups::{create_group, delete_group, group_add, group_remove, groups},
         list::list_items,
-        notes::edit_note,
+        mute::{mute, unmute},
+        notes::{add_note, notes, remove_note},
+        pending::{handle_acknowledge, pending},
         remove::{item_remove, remove_all},
+        remove_collection::remove_collection,
+        remove_multiple::remove_multiple,
         restart::restart,
         summary::changes_since,
+        trash::{trash, undo_remove},
+        watch::{unwatch, watch, watchlist},
+    },
+    settings::{
+        info::get_info, permissions::permissions, register_channel::*, set_check_interval::*,
+        set_escalation::*, set_hook::*, set_language::*, set_mention::*, set_notification_mode::*,
+        set_schedule::*, set_template::*, set_timezone::*, set_webhook::*, setup::setup,
     },
-    settings::{info::get_info, register_channel::*, set_schedule::*},
 };
 
+mod audit;
 mod commands;
 mod db;
+mod hooks;
+mod i18n;
 mod printer;
 mod scheduler;
 mod steam;
+mod template;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, AppState, Error>;
@@ -61,6 +80,8 @@ async fn main() {
 
     info!("Connected to DB");
 
+    db::migrations::migrate(&pool).expect("Failed to migrate the database");
+
     let options = poise::FrameworkOptions {
         commands: vec![
             help(),
@@ -74,23 +95,88 @@ async fn main() {
             get_info(),
             collection_add(),
             remove_all(),
-            edit_note(),
+            add_note(),
+            remove_note(),
+            notes(),
             changes_since(),
+            set_mention(),
+            set_webhook(),
+            set_hook(),
+            test_hook(),
+            set_notification_mode(),
+            set_template(),
+            set_timezone(),
+            set_language(),
+            preview_template(),
+            watch(),
+            unwatch(),
+            watchlist(),
+            pending(),
+            set_escalation(),
+            mark_deployed(),
+            pending_updates(),
+            create_group(),
+            delete_group(),
+            group_add(),
+            group_remove(),
+            groups(),
+            export(),
+            mute(),
+            unmute(),
+            set_check_interval(),
+            check_now(),
+            remove_multiple(),
+            remove_collection(),
+            trash(),
+            undo_remove(),
+            permissions(),
+            audit(),
+            setup(),
         ],
         on_error: |error| Box::pin(on_error(error)),
         pre_command: |ctx| {
             Box::pin(async move {
                 debug!("Executing command {}...", ctx.command().qualified_name);
+
+                // Remember what the command may change, so post_command can record it
+                if let Some(guild_id) = ctx.guild_id() {
+                    match audit::snapshot(
+                        &ctx.data().pool,
+                        guild_id.0,
+                        &ctx.command().qualified_name,
+                    ) {
+                        Ok(Some(snapshot)) => ctx.set_invocation_data(snapshot).await,
+                        Ok(None) => {}
+                        Err(e) => error!("Failed to take audit snapshot: {:?}", e),
+                    }
+                }
             })
         },
 
         post_command: |ctx| {
             Box::pin(async move {
                 debug!("Executed command {}!", ctx.command().qualified_name);
+
+                let guild_id = match ctx.guild_id() {
+                    Some(g) => g.0,
+                    None => return,
+                };
+
+                if let Some(before) = ctx.invocation_data::<audit::Snapshot>().await {
+                    if let Err(e) = audit::record(
+                        &ctx.data().pool,
+                        guild_id,
+                        ctx.author().id.0,
+                        &ctx.command().qualified_name,
+                        &before,
+                    ) {
+                        error!("Failed to record audit log: {:?}", e);
+                    }
+                }
             })
         },
-        /// This code is run after a command if it was successful (returned Ok)
-        event_handler: |_context, event, _framework, state| {
+        // This code is run after a command if it was successful (returned Ok)
+        event_handler: |context, event, _framework, state| {
             Box::pin(async move {
                 match event {
                     Event::GuildCreate { guild, is_new } => {
@@ -112,6 +198,14 @@ async fn main() {
 
                         Ok(())
                     }
+                    Event::InteractionCreate {
+                        interaction: Interaction::MessageComponent(mc),
+                    } if mc.data.custom_id.starts_with("ack:") => {
+                        if let Err(e) = handle_acknowledge(context, state, mc).await {
+                            error!("Failed to acknowledge update: {:?}", e);
+                        }
+                        Ok(())
+                    }
                     _ => Ok(()),
                 }
             })
@@ -195,6 +289,22 @@ async fn on_error(e: poise::FrameworkError<'_, AppState, Error>) {
         poise::FrameworkError::Command { error, ctx } => {
             error!("Error in command `{}`: {error:?}", ctx.command().name,);
         }
+        poise::FrameworkError::CommandCheckFailed { error, ctx } => {
+            let msg = match error {
+                Some(e) => {
+                    error!("Error while checking permissions: {e:?}");
+                    "An error occurred while checking your permissions."
+                }
+                None => "You don't have permission to use this command.",
+            };
+
+            if let Err(e) = ctx
+                .send(|m| m.content(i18n::tr!(ctx, msg)).ephemeral(true))
+                .await
+            {
+                error!("Error while handling error: {}", e);
+            }
+        }
         error => {
             if let Err(e) = poise::builtins::on_error(error).await {
                 error!("Error while handling error: {}", e);
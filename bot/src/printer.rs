@@ -1,40 +1,82 @@
-use std::time;
+use std::{collections::HashMap, time};
 
 use itertools::Itertools;
-use poise::serenity_prelude::{CacheHttp, CreateEmbed, GuildId};
+use mysql::Pool;
+use poise::serenity_prelude::{
+    ButtonStyle, CacheHttp, CreateAllowedMentions, CreateComponents, CreateEmbed, Embed,
+    Error as SerenityError, GuildChannel, GuildId, Http, HttpError, PrivateChannel, Timestamp,
+    UserId, Webhook,
+};
 use tracing::{info, warn};
 
 use crate::{
-    db::{self, ItemInfo},
+    db::{self, ItemInfo, Mention, NotificationMode, Provenance},
+    hooks,
+    i18n::{self, translate, Language},
     scheduler::Scheduler,
-    steam, Error,
+    steam, template, Error,
 };
 
-pub async fn notify_on_updates(scheduler: Scheduler, guild_id: u64) -> Result<(), Error> {
+/// The outcome of one update check
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CheckSummary {
+    pub checked: usize,
+    pub updated: usize,
+    pub failed: usize,
+    /// Items that were due, but had been posted too recently to be checked again
+    pub skipped: usize,
+}
+
+/// Checks every item of a guild that is due and posts its updates.
+/// A forced check looks at every item and leaves the schedule of the items untouched.
+pub async fn notify_on_updates(
+    scheduler: Scheduler,
+    guild_id: u64,
+    force: bool,
+) -> Result<CheckSummary, Error> {
     let client = scheduler.client.read().await;
 
     let client = match &*client {
         Some(c) => c,
         None => {
             warn!("Client not set, skipping update check");
-            return Ok(());
+            return Ok(CheckSummary::default());
         }
     };
 
+    // Checks of the same guild run one after another, each sees what the last one posted
+    let lock = scheduler.check_lock(guild_id);
+    let _guard = lock.lock().await;
+
+    let now = time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)?
+        .as_secs();
+
+    // The jobs don't tick exactly on time, so allow items to be checked a bit early
     let subscriptions =
-        db::subscriptions::get_all_subscriptions_of_guild(&scheduler.pool, guild_id)?;
+        db::subscriptions::get_due_subscriptions(&scheduler.pool, guild_id, now + 30, force)?;
+
+    if subscriptions.is_empty() {
+        return Ok(CheckSummary::default());
+    }
+
+    let checked: Vec<u64> = subscriptions.iter().map(|(_, i, _, _)| i.id).collect();
 
     let mut updated = Vec::new();
     let mut unknown = Vec::new();
     let mut failed = Vec::new();
+    let mut skipped = 0;
 
-    for (last_notify, item_info, note) in subscriptions {
-        // Only notify once per hour
-        if last_notify + 60 * 60
-            > time::SystemTime::now()
-                .duration_since(time::UNIX_EPOCH)?
-                .as_secs()
-        {
+    // The version this guild knew of before this check, for the outbound hook.
+    // Items.LastUpdate is shared by every guild, so it may already be newer.
+    let mut previous_updates = HashMap::new();
+
+    for (last_notify, item_info, note, interval) in subscriptions {
+        previous_updates.insert(item_info.id, last_notify);
+
+        // Only notify once per hour, unless the item is checked more often
+        if last_notify + (interval * 60).min(60 * 60) > now {
+            skipped += 1;
             continue;
         }
 
@@ -68,33 +110,123 @@ pub async fn notify_on_updates(scheduler: Scheduler, guild_id: u64) -> Result<()
         }
     }
 
-    let update_channel = db::servers::get_update_channel(&scheduler.pool, guild_id)?
-        .ok_or("No update channel set")?;
+    if !force {
+        db::subscriptions::update_last_checked(&scheduler.pool, guild_id, &checked, now)?;
+    }
 
-    let id = GuildId(guild_id);
-    let g = id.to_partial_guild(&client.http).await?;
+    let summary = CheckSummary {
+        checked: checked.len() - skipped,
+        updated: updated.len(),
+        failed: failed.len(),
+        skipped,
+    };
 
-    let channels = g.channels(&client.http).await?;
+    if updated.is_empty() && failed.is_empty() {
+        info!("No updates for guild: {}", guild_id);
+        return Ok(summary);
+    }
 
-    let c = match channels.iter().find(|c| c.0 .0 == update_channel) {
-        Some(c) => c.1,
-        None => {
-            return Err(format!(
-                "Update channel {} not found in guild {}",
-                update_channel, guild_id
-            )
-            .into())
-        }
-    };
+    let destination = Destination::of_guild(&scheduler.pool, &client.http, guild_id).await?;
+
+    let mode = db::servers::get_notification_mode(&scheduler.pool, guild_id)?;
+    let language = i18n::guild_language(&scheduler.pool, Some(guild_id));
+
+    // Muted items are still tracked, their updates are just not posted
+    let muted = db::subscriptions::get_muted_items(&scheduler.pool, guild_id)?;
+    let (muted_updates, mut updated): (Vec<_>, Vec<_>) = updated
+        .into_iter()
+        .partition(|(i, _)| muted.contains_key(&i.id));
+
+    for (item_info, _) in muted_updates {
+        info!(
+            "Not posting update of muted item {} for guild: {}",
+            item_info.id, guild_id
+        );
+        db::subscriptions::update_last_notify(&scheduler.pool, guild_id, item_info.id)?;
+    }
 
     if updated.is_empty() {
         info!("No updates for guild: {}", guild_id);
     } else {
         info!("Found {} updates for guild: {}", updated.len(), guild_id);
-        if updated.len() > 5 {
-            send_in_chunks("The following items were updated:", c, client, &updated).await?;
-        } else {
-            send_in_one("The following items were updated:", c, client, &updated).await?;
+
+        match mode {
+            NotificationMode::Immediate => {
+                let mentions = get_mentions(&scheduler, guild_id, &updated)?;
+                let templates = db::servers::get_templates(&scheduler.pool, guild_id)?;
+                let groups = db::groups::get_item_groups(&scheduler.pool, guild_id)?;
+                let provenance = get_provenance(&scheduler.pool, &client, guild_id).await?;
+                let msg = templates.header(
+                    translate(language, "The following items were updated:"),
+                    updated.len(),
+                );
+
+                // Items of the same group are posted next to each other
+                sort_by_group(&mut updated, &groups);
+
+                if updated.len() > 5 {
+                    send_in_chunks(
+                        &msg,
+                        &destination,
+                        client,
+                        &updated,
+                        MessageOptions {
+                            mentions: &mentions,
+                            item_template: templates.item.as_deref(),
+                            groups: Some(&groups),
+                            provenance: Some(&provenance),
+                            acknowledge: destination.can_acknowledge(),
+                            language,
+                        },
+                    )
+                    .await?;
+                } else {
+                    send_in_one(
+                        &msg,
+                        &destination,
+                        client,
+                        &updated,
+                        MessageOptions {
+                            mentions: &mentions,
+                            item_template: templates.item.as_deref(),
+                            groups: Some(&groups),
+                            provenance: Some(&provenance),
+                            acknowledge: destination.can_acknowledge(),
+                            language,
+                        },
+                    )
+                    .await?;
+                }
+
+                if destination.can_acknowledge() {
+                    record_events(&scheduler.pool, guild_id, &updated)?;
+                }
+            }
+            NotificationMode::Daily | NotificationMode::Weekly => {
+                // Posted with the next digest instead
+                let ids: Vec<u64> = updated.iter().map(|(i, _)| i.id).collect();
+                db::digests::add_pending_updates(&scheduler.pool, guild_id, &ids)?;
+            }
+        }
+
+        hooks::send_update_events(
+            &scheduler.pool,
+            guild_id,
+            updated
+                .iter()
+                .map(|(item_info, note)| hooks::UpdateEvent {
+                    previous_update: previous_updates
+                        .get(&item_info.id)
+                        .copied()
+                        .unwrap_or(item_info.last_updated),
+                    item_info: item_info.clone(),
+                    note: note.clone(),
+                })
+                .collect(),
+        )?;
+
+        if let Err(e) = notify_watchers(&scheduler.pool, client, &updated).await {
+            warn!("Failed to notify watchers for guild {}: {}", guild_id, e);
         }
 
         for (item_info, _) in updated {
@@ -102,34 +234,570 @@ pub async fn notify_on_updates(scheduler: Scheduler, guild_id: u64) -> Result<()
         }
     }
 
-    if !failed.is_empty() {
+    if !failed.is_empty() && mode != NotificationMode::Immediate {
+        warn!(
+            "Failed to check {} items for guild: {}",
+            failed.len(),
+            guild_id
+        );
+    } else if !failed.is_empty() {
         if failed.len() > 5 {
             send_in_chunks(
-                "The following Items could not be updated:",
-                c,
+                translate(language, "The following Items could not be updated:"),
+                &destination,
                 client,
                 &failed,
+                MessageOptions {
+                    language,
+                    ..Default::default()
+                },
             )
             .await?;
         } else {
             send_in_one(
-                "The following Items could not be updated:",
-                c,
+                translate(language, "The following Items could not be updated:"),
+                &destination,
                 client,
                 &failed,
+                MessageOptions {
+                    language,
+                    ..Default::default()
+                },
             )
             .await?
         }
     }
 
+    Ok(summary)
+}
+
+/// Posts all updates collected since the last digest as one summary
+pub async fn send_digest(scheduler: Scheduler, guild_id: u64) -> Result<(), Error> {
+    let client = scheduler.client.read().await;
+
+    let client = match &*client {
+        Some(c) => c,
+        None => {
+            warn!("Client not set, skipping digest");
+            return Ok(());
+        }
+    };
+
+    let until = time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)?
+        .as_secs();
+
+    let pending = db::digests::get_pending_updates(&scheduler.pool, guild_id)?;
+
+    if pending.is_empty() {
+        info!("No updates for digest of guild: {}", guild_id);
+    } else {
+        info!(
+            "Sending digest of {} items for guild: {}",
+            pending.len(),
+            guild_id
+        );
+
+        let language = i18n::guild_language(&scheduler.pool, Some(guild_id));
+
+        let mut updated: Vec<(ItemInfo, Option<String>)> = pending
+            .into_iter()
+            .map(|(mut item_info, count, note)| {
+                if count > 1 {
+                    item_info.name = template::render(
+                        translate(language, "{name} (updated {count} times)"),
+                        &[("name", item_info.name), ("count", count.to_string())],
+                    );
+                }
+                (item_info, note)
+            })
+            .collect();
+
+        let destination = Destination::of_guild(&scheduler.pool, &client.http, guild_id).await?;
+        let mentions = get_mentions(&scheduler, guild_id, &updated)?;
+
+        let templates = db::servers::get_templates(&scheduler.pool, guild_id)?;
+        let groups = db::groups::get_item_groups(&scheduler.pool, guild_id)?;
+        let provenance = get_provenance(&scheduler.pool, &client, guild_id).await?;
+        sort_by_group(&mut updated, &groups);
+
+        let msg = match db::servers::get_last_digest(&scheduler.pool, guild_id)? {
+            Some(last) => template::render(
+                translate(
+                    language,
+                    "The following items were updated since <t:{since}:f>:",
+                ),
+                &[("since", last.to_string())],
+            ),
+            None => translate(language, "The following items were updated:").to_string(),
+        };
+        let msg = templates.header(&msg, updated.len());
+
+        if updated.len() > 5 {
+            send_in_chunks(
+                &msg,
+                &destination,
+                client,
+                &updated,
+                MessageOptions {
+                    mentions: &mentions,
+                    item_template: templates.item.as_deref(),
+                    groups: Some(&groups),
+                    provenance: Some(&provenance),
+                    acknowledge: destination.can_acknowledge(),
+                    language,
+                },
+            )
+            .await?;
+        } else {
+            send_in_one(
+                &msg,
+                &destination,
+                client,
+                &updated,
+                MessageOptions {
+                    mentions: &mentions,
+                    item_template: templates.item.as_deref(),
+                    groups: Some(&groups),
+                    provenance: Some(&provenance),
+                    acknowledge: destination.can_acknowledge(),
+                    language,
+                },
+            )
+            .await?;
+        }
+
+        if destination.can_acknowledge() {
+            record_events(&scheduler.pool, guild_id, &updated)?;
+        }
+        db::digests::clear_pending_updates(&scheduler.pool, guild_id, until)?;
+    }
+
+    db::servers::update_last_digest_timestamp(&scheduler.pool, guild_id)?;
+
     Ok(())
 }
 
+/// Re-posts updates nobody handled in time and pings the escalation role of their guild
+pub async fn send_reminders(scheduler: Scheduler) -> Result<(), Error> {
+    let client = scheduler.client.read().await;
+
+    let client = match &*client {
+        Some(c) => c,
+        None => {
+            warn!("Client not set, skipping reminders");
+            return Ok(());
+        }
+    };
+
+    let due = db::events::get_due_reminders(&scheduler.pool)?;
+    let mut done = Vec::new();
+
+    for (guild_id, reminders) in due.into_iter().map(|r| (r.0, r)).into_group_map() {
+        match send_guild_reminders(&scheduler, client, guild_id, &reminders).await {
+            Ok(_) => done.extend(reminders),
+            Err(e) => warn!("Failed to send reminders for guild {}: {}", guild_id, e),
+        }
+    }
+
+    // Also cleans up reminders of updates that were handled in the meantime
+    db::events::remove_stale_reminders(&scheduler.pool, &done)
+}
+
+async fn send_guild_reminders(
+    scheduler: &Scheduler,
+    client: impl CacheHttp,
+    guild_id: u64,
+    reminders: &[(u64, u64, u64)],
+) -> Result<(), Error> {
+    let (hours, role_id) = match db::events::get_escalation_rule(&scheduler.pool, guild_id)? {
+        Some(rule) => rule,
+        // The rule was removed after the reminders were scheduled
+        None => return Ok(()),
+    };
+
+    let mut updated = Vec::new();
+
+    for (_, item_id, _) in reminders {
+        let item_info = match db::items::get_item(&scheduler.pool, *item_id)? {
+            Some(i) => i,
+            None => continue,
+        };
+
+        // The item is no longer tracked
+        let note = match db::subscriptions::get_note(&scheduler.pool, guild_id, *item_id) {
+            Ok(note) => note,
+            Err(_) => continue,
+        };
+
+        updated.push((item_info, note));
+    }
+
+    if updated.is_empty() {
+        return Ok(());
+    }
+
+    info!(
+        "Escalating {} unhandled updates for guild: {}",
+        updated.len(),
+        guild_id
+    );
+
+    let destination = Destination::of_guild(&scheduler.pool, client.http(), guild_id).await?;
+    let templates = db::servers::get_templates(&scheduler.pool, guild_id)?;
+    let groups = db::groups::get_item_groups(&scheduler.pool, guild_id)?;
+    let provenance = get_provenance(&scheduler.pool, &client, guild_id).await?;
+    let mentions = [Mention::Role(role_id)];
+
+    let language = i18n::guild_language(&scheduler.pool, Some(guild_id));
+
+    let msg = template::render(
+        translate(
+            language,
+            "The following updates were not handled within {hours} hours:",
+        ),
+        &[("hours", hours.to_string())],
+    );
+    let options = MessageOptions {
+        mentions: &mentions,
+        item_template: templates.item.as_deref(),
+        groups: Some(&groups),
+        provenance: Some(&provenance),
+        acknowledge: destination.can_acknowledge(),
+        language,
+    };
+
+    if updated.len() > 5 {
+        send_in_chunks(&msg, &destination, client, &updated, options).await
+    } else {
+        send_in_one(&msg, &destination, client, &updated, options).await
+    }
+}
+
+/// Posted updates wait for someone to acknowledge them
+fn record_events(
+    pool: &Pool,
+    guild_id: u64,
+    updated: &[(ItemInfo, Option<String>)],
+) -> Result<(), Error> {
+    let versions: Vec<(u64, u64)> = updated
+        .iter()
+        .map(|(i, _)| (i.id, i.last_updated))
+        .collect();
+
+    db::events::add_events(pool, guild_id, &versions)
+}
+
+/// Sends every user watching one of the updated items a single DM with all of their items
+async fn notify_watchers(
+    pool: &Pool,
+    client: impl CacheHttp,
+    updated: &[(ItemInfo, Option<String>)],
+) -> Result<(), Error> {
+    let versions: Vec<(u64, u64)> = updated
+        .iter()
+        .map(|(i, _)| (i.id, i.last_updated))
+        .collect();
+
+    let watchers = db::watches::get_watchers_to_notify(pool, &versions)?
+        .into_iter()
+        .into_group_map();
+
+    for ((user_id, language), item_ids) in watchers {
+        // Notes belong to the guild, so they are not sent to users
+        let items: Vec<(ItemInfo, Option<String>)> = updated
+            .iter()
+            .filter(|(i, _)| item_ids.contains(&i.id))
+            .map(|(i, _)| (i.clone(), None))
+            .collect();
+
+        let res = match UserId(user_id).create_dm_channel(&client).await {
+            Ok(c) => {
+                let destination = Destination::Dm(c);
+                let msg = translate(language, "Items on your watchlist were updated:");
+                let options = MessageOptions {
+                    language,
+                    ..MessageOptions::default()
+                };
+
+                if items.len() > 5 {
+                    send_in_chunks(msg, &destination, &client, &items, options).await
+                } else {
+                    send_in_one(msg, &destination, &client, &items, options).await
+                }
+            }
+            Err(e) => Err(e.into()),
+        };
+
+        match res {
+            Ok(_) => {
+                for (item_info, _) in items {
+                    db::watches::update_last_notified(
+                        pool,
+                        user_id,
+                        item_info.id,
+                        item_info.last_updated,
+                    )?;
+                }
+            }
+            Err(e) if is_dm_closed(&e) => {
+                info!(
+                    "User {} has closed their DMs, pausing their watchlist",
+                    user_id
+                );
+                db::watches::set_dm_closed(pool, user_id)?;
+            }
+            Err(e) => warn!("Failed to DM user {}: {}", user_id, e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Discord answers with code 50007 if we may not DM a user
+fn is_dm_closed(e: &Error) -> bool {
+    match e.downcast_ref::<SerenityError>() {
+        Some(SerenityError::Http(e)) => {
+            matches!(&**e, HttpError::UnsuccessfulRequest(r) if r.error.code == 50007)
+        }
+        _ => false,
+    }
+}
+
+/// Where the update messages of a guild are posted to
+pub enum Destination {
+    Channel(GuildChannel),
+    Dm(PrivateChannel),
+    Webhook {
+        webhook: Webhook,
+        name: Option<String>,
+        avatar_url: Option<String>,
+    },
+}
+
+impl Destination {
+    /// Uses the webhook of the guild if one is registered, otherwise the update channel
+    pub async fn of_guild(pool: &Pool, http: &Http, guild_id: u64) -> Result<Self, Error> {
+        if let Some((url, name, avatar_url)) = db::servers::get_webhook(pool, guild_id)? {
+            let webhook = Webhook::from_url(http, &url).await?;
+
+            return Ok(Destination::Webhook {
+                webhook,
+                name,
+                avatar_url,
+            });
+        }
+
+        let update_channel =
+            db::servers::get_update_channel(pool, guild_id)?.ok_or("No update channel set")?;
+
+        let channels = GuildId(guild_id).channels(http).await?;
+
+        match channels.into_iter().find(|c| c.0 .0 == update_channel) {
+            Some((_, c)) => Ok(Destination::Channel(c)),
+            None => Err(format!(
+                "Update channel {} not found in guild {}",
+                update_channel, guild_id
+            )
+            .into()),
+        }
+    }
+
+    /// Only messages of the bot itself can carry the buttons to handle an update,
+    /// so updates posted through a webhook are not tracked as pending
+    pub fn can_acknowledge(&self) -> bool {
+        matches!(self, Destination::Channel(_))
+    }
+
+    async fn send(
+        &self,
+        client: impl CacheHttp,
+        content: String,
+        embeds: Vec<CreateEmbed>,
+        mentions: &[Mention],
+        components: Option<CreateComponents>,
+    ) -> Result<(), Error> {
+        match self {
+            Destination::Channel(c) => {
+                c.send_message(&client, |d| {
+                    d.content(content);
+                    d.set_embeds(embeds);
+                    d.allowed_mentions(|am| {
+                        allowed_mentions(am, mentions);
+                        am
+                    });
+                    // Only messages of the bot itself can carry buttons
+                    if let Some(components) = components {
+                        d.set_components(components);
+                    }
+                    d
+                })
+                .await?;
+            }
+            Destination::Dm(c) => {
+                c.send_message(client.http(), |d| {
+                    d.content(content);
+                    d.set_embeds(embeds);
+                    d.allowed_mentions(|am| {
+                        allowed_mentions(am, mentions);
+                        am
+                    });
+                    d
+                })
+                .await?;
+            }
+            Destination::Webhook {
+                webhook,
+                name,
+                avatar_url,
+            } => {
+                webhook
+                    .execute(client.http(), false, |w| {
+                        w.content(content);
+                        w.embeds(
+                            embeds
+                                .into_iter()
+                                .map(|embed| {
+                                    Embed::fake(|e| {
+                                        *e = embed;
+                                        e
+                                    })
+                                })
+                                .collect(),
+                        );
+                        w.allowed_mentions(|am| {
+                            allowed_mentions(am, mentions);
+                            am
+                        });
+                        if let Some(name) = name {
+                            w.username(name);
+                        }
+                        if let Some(url) = avatar_url {
+                            w.avatar_url(url);
+                        }
+                        w
+                    })
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects everyone that should be pinged for the given updates, without duplicates
+fn get_mentions(
+    scheduler: &Scheduler,
+    guild_id: u64,
+    updated: &[(ItemInfo, Option<String>)],
+) -> Result<Vec<Mention>, Error> {
+    let mut mentions = db::servers::get_mentions(&scheduler.pool, guild_id)?;
+
+    let item_mentions = db::subscriptions::get_item_mentions(&scheduler.pool, guild_id)?
+        .into_iter()
+        .chain(db::groups::get_group_mentions(&scheduler.pool, guild_id)?);
+
+    for (item_id, mention) in item_mentions {
+        if updated.iter().any(|(i, _)| i.id == item_id) && !mentions.contains(&mention) {
+            mentions.push(mention);
+        }
+    }
+
+    Ok(mentions)
+}
+
+fn with_mentions(msg: &str, mentions: &[Mention]) -> String {
+    if mentions.is_empty() {
+        return msg.to_string();
+    }
+
+    format!("{}\n{}", mentions.iter().join(" "), msg)
+}
+
+/// Only the given mentions may ping anyone, so names and notes can never ping by accident
+fn allowed_mentions(am: &mut CreateAllowedMentions, mentions: &[Mention]) {
+    am.empty_parse();
+    am.roles(mentions.iter().filter_map(|m| match m {
+        Mention::Role(id) => Some(*id),
+        Mention::User(_) => None,
+    }));
+    am.users(mentions.iter().filter_map(|m| match m {
+        Mention::User(id) => Some(*id),
+        Mention::Role(_) => None,
+    }));
+}
+
+/// How the items of an update message are presented
+#[derive(Clone, Copy, Default)]
+pub struct MessageOptions<'a> {
+    /// Pinged with the first message
+    pub mentions: &'a [Mention],
+    pub item_template: Option<&'a str>,
+    /// The groups of every item, shown in their embeds
+    pub groups: Option<&'a HashMap<u64, Vec<String>>>,
+    /// Who added every item, shown in the footers of their embeds
+    pub provenance: Option<&'a HashMap<u64, Provenance>>,
+    /// Adds buttons to mark the updates as handled
+    pub acknowledge: bool,
+    pub language: Language,
+}
+
+impl MessageOptions<'_> {
+    fn groups_of(&self, item_id: u64) -> &[String] {
+        self.groups
+            .and_then(|g| g.get(&item_id))
+            .map(|g| g.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn provenance_of(&self, item_id: u64) -> Option<&Provenance> {
+        self.provenance.and_then(|p| p.get(&item_id))
+    }
+}
+
+/// Loads who added the items of a guild, with the names of the users for the embed footers
+pub async fn get_provenance(
+    pool: &Pool,
+    client: &impl CacheHttp,
+    guild_id: u64,
+) -> Result<HashMap<u64, Provenance>, Error> {
+    let mut provenance = db::subscriptions::get_provenance(pool, guild_id)?;
+    let mut names: HashMap<u64, Option<String>> = HashMap::new();
+
+    for p in provenance.values_mut() {
+        let user = match p.added_by {
+            Some(u) => u,
+            None => continue,
+        };
+
+        p.added_by_name = match names.get(&user) {
+            Some(name) => name.clone(),
+            None => {
+                // Users that can't be fetched anymore are left out of the footer
+                let name = UserId(user).to_user(client).await.ok().map(|u| u.name);
+                names.insert(user, name.clone());
+                name
+            }
+        };
+    }
+
+    Ok(provenance)
+}
+
+/// Orders items by the name of their first group, items without a group come last
+pub fn sort_by_group(items: &mut [(ItemInfo, Option<String>)], groups: &HashMap<u64, Vec<String>>) {
+    items.sort_by_key(|(i, _)| match groups.get(&i.id).and_then(|g| g.first()) {
+        Some(group) => (false, group.clone()),
+        None => (true, String::new()),
+    });
+}
+
 pub async fn send_in_chunks(
     msg: &str,
-    c: &poise::serenity_prelude::GuildChannel,
+    destination: &Destination,
     client: impl CacheHttp,
     updated: &[(db::ItemInfo, Option<String>)],
+    options: MessageOptions<'_>,
 ) -> Result<(), Error> {
     let chunks: Vec<Vec<(db::ItemInfo, Option<String>)>> = updated
         .iter()
@@ -141,19 +809,46 @@ pub async fn send_in_chunks(
     let parts = chunks.len();
 
     for (curr, chunk) in chunks.iter().enumerate() {
-        c.send_message(&client, |d| {
-            d.content(format!("{}\nPart {}/{}", msg, curr + 1, parts));
-
-            for (item_info, note) in chunk.iter() {
-                d.add_embed(|e| {
-                    item_to_embed(e, item_info, note);
-                    e
-                });
-            }
+        // Only ping on the first part
+        let mentions = if curr == 0 { options.mentions } else { &[] };
 
-            d
-        })
-        .await?;
+        destination
+            .send(
+                &client,
+                with_mentions(
+                    &format!(
+                        "{}\n{}",
+                        msg,
+                        template::render(
+                            translate(options.language, "Part {part}/{parts}"),
+                            &[
+                                ("part", (curr + 1).to_string()),
+                                ("parts", parts.to_string())
+                            ],
+                        )
+                    ),
+                    mentions,
+                ),
+                chunk
+                    .iter()
+                    .map(|(i, n)| {
+                        item_to_embed(
+                            i,
+                            n,
+                            options.item_template,
+                            updated.len(),
+                            options.groups_of(i.id),
+                            options.provenance_of(i.id),
+                            options.language,
+                        )
+                    })
+                    .collect(),
+                mentions,
+                options
+                    .acknowledge
+                    .then(|| acknowledge_buttons(chunk, options.language)),
+            )
+            .await?;
     }
 
     Ok(())
@@ -161,27 +856,136 @@ pub async fn send_in_chunks(
 
 pub async fn send_in_one(
     msg: &str,
-    c: &poise::serenity_prelude::GuildChannel,
+    destination: &Destination,
     client: impl CacheHttp,
     updated: &[(db::ItemInfo, Option<String>)],
+    options: MessageOptions<'_>,
 ) -> Result<(), Error> {
-    c.send_message(&client, |d| {
-        d.content(msg);
+    destination
+        .send(
+            &client,
+            with_mentions(msg, options.mentions),
+            updated
+                .iter()
+                .map(|(i, n)| {
+                    item_to_embed(
+                        i,
+                        n,
+                        options.item_template,
+                        updated.len(),
+                        options.groups_of(i.id),
+                        options.provenance_of(i.id),
+                        options.language,
+                    )
+                })
+                .collect(),
+            options.mentions,
+            options
+                .acknowledge
+                .then(|| acknowledge_buttons(updated, options.language)),
+        )
+        .await
+}
+
+/// One row per item, with buttons to mark its update as handled or to snooze it.
+/// The ids are handled by [`crate::commands::actions::pending::handle_acknowledge`].
+fn acknowledge_buttons(
+    items: &[(ItemInfo, Option<String>)],
+    language: Language,
+) -> CreateComponents {
+    let mut c = CreateComponents::default();
 
-        for (item_info, note) in updated.iter() {
-            d.add_embed(|e| {
-                item_to_embed(e, item_info, note);
-                e
+    for (item_info, _) in items {
+        c.create_action_row(|r| {
+            r.create_button(|b| {
+                b.style(ButtonStyle::Success);
+                b.label(truncate(
+                    &template::render(
+                        translate(language, "Handled: {name}"),
+                        &[("name", item_info.name.clone())],
+                    ),
+                    80,
+                ));
+                b.custom_id(format!(
+                    "ack:handled:{}:{}",
+                    item_info.id, item_info.last_updated
+                ));
+                b
             });
+            r.create_button(|b| {
+                b.style(ButtonStyle::Secondary);
+                b.label(translate(language, "Snooze 1 day"));
+                b.custom_id(format!(
+                    "ack:snooze:{}:{}",
+                    item_info.id, item_info.last_updated
+                ));
+                b
+            });
+            r
+        });
+    }
+
+    c
+}
+
+/// Discord rejects messages with more characters than this
+pub const MESSAGE_LIMIT: usize = 2000;
+
+/// Joins the lines into as few messages as possible, each starting with the header.
+/// Lines too long for a message of their own are truncated.
+pub fn split_message(header: &str, lines: &[String]) -> Vec<String> {
+    let header_len = header.chars().count();
+    let budget = MESSAGE_LIMIT.saturating_sub(header_len + 1).max(1);
+
+    let mut messages = Vec::new();
+    let mut current = header.to_string();
+    let mut len = header_len;
+
+    for line in lines {
+        let line = truncate(line, budget);
+        let line_len = line.chars().count();
+
+        if len > header_len && len + 1 + line_len > MESSAGE_LIMIT {
+            messages.push(std::mem::replace(&mut current, header.to_string()));
+            len = header_len;
         }
 
-        d
-    })
-    .await?;
-    Ok(())
+        current.push('\n');
+        current.push_str(&line);
+        len += 1 + line_len;
+    }
+
+    if len > header_len {
+        messages.push(current);
+    }
+
+    messages
+}
+
+pub fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+
+    let mut res: String = s.chars().take(max - 1).collect();
+    res.push('…');
+    res
 }
 
-fn item_to_embed(e: &mut CreateEmbed, item_info: &ItemInfo, note: &Option<String>) {
+/// The embeds of a message share 6000 characters, so long notes are cut short in the footer
+const FOOTER_NOTE_LENGTH: usize = 400;
+
+pub fn item_to_embed(
+    item_info: &ItemInfo,
+    note: &Option<String>,
+    item_template: Option<&str>,
+    count: usize,
+    groups: &[String],
+    provenance: Option<&Provenance>,
+    language: Language,
+) -> CreateEmbed {
+    let mut e = CreateEmbed::default();
+
     e.title(&item_info.name);
     e.url(format!(
         "https://steamcommunity.com/sharedfiles/filedetails/?id={}",
@@ -192,10 +996,107 @@ fn item_to_embed(e: &mut CreateEmbed, item_info: &ItemInfo, note: &Option<String
         e.image(url);
     }
 
-    if let Some(note) = note {
+    // Discord renders these in the timezone of every reader
+    e.field(
+        translate(language, "Updated"),
+        format!("<t:{}:R>", item_info.last_updated),
+        true,
+    );
+
+    if !groups.is_empty() {
+        e.field(translate(language, "Groups"), groups.join(", "), true);
+    }
+
+    let mut footer = vec![];
+
+    if let Some(template) = item_template {
+        e.description(template::render(
+            template,
+            &template::item_values(item_info, note, count),
+        ));
+    } else if let Some(note) = note {
+        // Items can have several notes, one per line
+        let label = match note.contains('\n') {
+            true => "Notes:\n{note}",
+            false => "Note: {note}",
+        };
+        footer.push(template::render(
+            translate(language, label),
+            &[("note", truncate(note, FOOTER_NOTE_LENGTH))],
+        ));
+    }
+
+    if let Some(provenance) = provenance {
+        let added = match (&provenance.added_by_name, provenance.collection) {
+            (Some(user), Some(collection)) => Some(template::render(
+                translate(language, "Added by {user} from collection {collection}"),
+                &[
+                    ("user", user.clone()),
+                    ("collection", collection.to_string()),
+                ],
+            )),
+            (Some(user), None) => Some(template::render(
+                translate(language, "Added by {user}"),
+                &[("user", user.clone())],
+            )),
+            (None, Some(collection)) => Some(template::render(
+                translate(language, "Added from collection {collection}"),
+                &[("collection", collection.to_string())],
+            )),
+            (None, None) => None,
+        };
+        footer.extend(added);
+
+        // Shown next to the footer in the timezone of every reader
+        if let Some(added_at) = provenance.added_at {
+            if let Ok(timestamp) = Timestamp::from_unix_timestamp(added_at as i64) {
+                e.timestamp(timestamp);
+            }
+        }
+    }
+
+    if !footer.is_empty() {
         e.footer(|f| {
-            f.text(format!("Note: {}", note));
+            f.text(footer.join("\n"));
             f
         });
     }
+
+    e
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_message_keeps_every_message_within_the_limit() {
+        let lines: Vec<String> = (0..100).map(|i| format!("{:0>90}", i)).collect();
+
+        let messages = split_message("Header:", &lines);
+
+        assert!(messages.len() > 1);
+        for message in &messages {
+            assert!(message.chars().count() <= MESSAGE_LIMIT);
+            assert!(message.starts_with("Header:\n"));
+        }
+
+        let joined: Vec<&str> = messages.iter().flat_map(|m| m.lines().skip(1)).collect();
+        assert_eq!(joined, lines);
+    }
+
+    #[test]
+    fn split_message_truncates_long_lines() {
+        let messages = split_message("Header:", &["x".repeat(3000), "y".to_string()]);
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].chars().count(), MESSAGE_LIMIT);
+        assert!(messages[0].ends_with('…'));
+        assert_eq!(messages[1], "Header:\ny");
+    }
+
+    #[test]
+    fn split_message_without_lines_is_empty() {
+        assert!(split_message("Header:", &[]).is_empty());
+    }
 }
@@ -1,17 +1,25 @@
 use std::{sync::Arc, time::Duration};
 
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
 use dashmap::DashMap;
 use mysql::Pool;
 use poise::serenity_prelude::CacheAndHttp;
-use tokio::{sync::RwLock, task::JoinHandle, time::sleep};
+use tokio::{
+    sync::{Mutex, RwLock},
+    task::JoinHandle,
+    time::sleep,
+};
 use tracing::{debug, error, info, warn};
 
-use crate::{db, Error};
+use crate::{db, db::NotificationMode, Error};
 
 #[derive(Clone)]
 pub struct Scheduler {
     pub client: Arc<RwLock<Option<Arc<CacheAndHttp>>>>,
     pub jobs: Arc<DashMap<u64, JoinHandle<()>>>,
+    /// Held while the items of a guild are checked, so a forced check and the job don't post an update twice
+    pub checks: Arc<DashMap<u64, Arc<Mutex<()>>>>,
     pub pool: Arc<Pool>,
 }
 
@@ -20,6 +28,7 @@ impl Scheduler {
         Self {
             client: Arc::new(RwLock::new(None)),
             jobs: Arc::new(DashMap::new()),
+            checks: Arc::new(DashMap::new()),
             pool,
         }
     }
@@ -34,6 +43,10 @@ impl Scheduler {
     pub async fn start_cron(&self, client: Arc<CacheAndHttp>) -> Result<(), Error> {
         *self.client.write().await = Some(client);
 
+        self.start_digest_job();
+        self.start_reminder_job();
+        self.start_purge_job();
+
         let schedules = db::servers::get_all_schedules(&self.pool)?;
         let count = schedules.len();
 
@@ -43,9 +56,9 @@ impl Scheduler {
         );
 
         for (guild_id, schedule) in schedules {
-            if let Some(hours) = schedule {
+            if schedule.is_some() {
                 let s = self.clone();
-                s.start_job(guild_id, hours);
+                s.start_job(guild_id);
 
                 // Spread out the registrations so we don't hit any rate limits
                 sleep(Duration::from_secs(((60 * 30) / count) as u64)).await;
@@ -57,19 +70,22 @@ impl Scheduler {
     }
 
     pub async fn start_schedule(&self, guild_id: u64) -> Result<(), Error> {
-        let hours = db::servers::get_schedule(&self.pool, guild_id)?
+        db::servers::get_schedule(&self.pool, guild_id)?
             .ok_or("No schedule set for this server.")?;
 
+        // A (re)started schedule checks every item right away
+        db::subscriptions::reset_last_checked(&self.pool, guild_id)?;
+
         let s = self.clone();
-        s.start_job(guild_id, hours);
+        s.start_job(guild_id);
 
         Ok(())
     }
 
-    fn start_job(self, guild_id: u64, hours: u64) {
+    fn start_job(self, guild_id: u64) {
         let s = self.clone();
         let h = tokio::spawn(async move {
-            match work_loop(s.clone(), guild_id, hours).await {
+            match work_loop(s.clone(), guild_id).await {
                 Ok(_) => {}
                 Err(e) => {
                     error!(
@@ -89,16 +105,70 @@ impl Scheduler {
         }
     }
 
+    fn start_digest_job(&self) {
+        let s = self.clone();
+        tokio::spawn(digest_loop(s));
+    }
+
+    fn start_reminder_job(&self) {
+        let s = self.clone();
+        tokio::spawn(async move {
+            info!("Starting reminder job");
+
+            // Reminders live in the DB, so none are lost if the bot restarts
+            let mut interval = tokio::time::interval(Duration::from_secs(60 * 5));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = crate::printer::send_reminders(s.clone()).await {
+                    error!("Error while sending reminders: {}", e);
+                }
+            }
+        });
+    }
+
+    fn start_purge_job(&self) {
+        let s = self.clone();
+        tokio::spawn(async move {
+            info!("Starting trash purge job");
+
+            let mut interval = tokio::time::interval(Duration::from_secs(60 * 60));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                interval.tick().await;
+
+                match db::subscriptions::purge_trash(&s.pool) {
+                    Ok(0) => {}
+                    Ok(count) => info!("Purged {} items from the trash", count),
+                    Err(e) => error!("Error while purging the trash: {}", e),
+                }
+            }
+        });
+    }
+
+    pub fn check_lock(&self, guild_id: u64) -> Arc<Mutex<()>> {
+        self.checks.entry(guild_id).or_default().clone()
+    }
+
     pub fn is_running(&self, guild_id: u64) -> bool {
         debug!(guild_id, "Checking if tracking job is running");
         self.jobs.contains_key(&guild_id)
     }
 }
 
-async fn work_loop(s: Scheduler, guild_id: u64, hours: u64) -> Result<(), Error> {
+/// How often the tracking jobs look for items that are due to be checked
+const CHECK_TICK: Duration = Duration::from_secs(60);
+
+async fn work_loop(s: Scheduler, guild_id: u64) -> Result<(), Error> {
     info!("Starting tracking job for guild: {}", guild_id);
 
-    let mut interval = tokio::time::interval(Duration::from_secs(60 * 60 * hours));
+    db::servers::update_last_restart_timestamp(&s.pool, guild_id)?;
+
+    // Every item is checked on its own interval, the schedule of the guild being the default
+    let mut interval = tokio::time::interval(CHECK_TICK);
 
     // We use delay as we don't care about precision, we just want to tick every so often
     interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
@@ -106,14 +176,37 @@ async fn work_loop(s: Scheduler, guild_id: u64, hours: u64) -> Result<(), Error>
     loop {
         interval.tick().await;
 
-        if !db::servers::check_still_in_guild(&s.pool, guild_id)? {
-            warn!(
-                "Guild {} is no longer in the guild list, stopping tracking job",
-                guild_id
-            );
-            break;
+        // A failed tick is logged and retried on the next one, e.g. if the DB was briefly unreachable
+        match db::servers::check_still_in_guild(&s.pool, guild_id) {
+            Ok(true) => {}
+            Ok(false) => {
+                warn!(
+                    "Guild {} is no longer in the guild list, stopping tracking job",
+                    guild_id
+                );
+                break;
+            }
+            Err(e) => {
+                error!(
+                    "Error while checking the guild list: {}, for server: {}",
+                    e, guild_id
+                );
+                continue;
+            }
         }
-        match crate::printer::notify_on_updates(s.clone(), guild_id).await {
+        match crate::printer::notify_on_updates(s.clone(), guild_id, false).await {
+            Ok(summary) if summary.checked + summary.skipped > 0 => {
+                info!(
+                    "Checked {} items for guild: {}, {} updated, {} failed, {} skipped",
+                    summary.checked, guild_id, summary.updated, summary.failed, summary.skipped
+                );
+                if let Err(e) = db::servers::update_last_update_timestamp(&s.pool, guild_id) {
+                    error!(
+                        "Error while updating the last update: {}, for server: {}",
+                        e, guild_id
+                    );
+                }
+            }
             Ok(_) => {}
             Err(e) => {
                 tracing::error!(
@@ -121,11 +214,138 @@ async fn work_loop(s: Scheduler, guild_id: u64, hours: u64) -> Result<(), Error>
                     e,
                     guild_id
                 );
-                break;
             }
         }
-        db::servers::update_last_update_timestamp(&s.pool, guild_id)?;
     }
 
     Ok(())
 }
+
+async fn digest_loop(s: Scheduler) {
+    info!("Starting digest job");
+
+    let mut interval = tokio::time::interval(Duration::from_secs(60 * 5));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        // A failed tick is logged and retried on the next one
+        let schedules = match db::servers::get_digest_schedules(&s.pool) {
+            Ok(schedules) => schedules,
+            Err(e) => {
+                error!("Error while fetching the digest schedules: {}", e);
+                continue;
+            }
+        };
+
+        for (guild_id, mode, hour, day, tz, last_digest) in schedules {
+            let due = last_digest_due(mode, hour, day, Utc::now().with_timezone(&tz));
+
+            if last_digest.unwrap_or(0) as i64 >= due {
+                continue;
+            }
+
+            if let Err(e) = crate::printer::send_digest(s.clone(), guild_id).await {
+                error!(
+                    "Error while sending digest: {}, for server: {}",
+                    e, guild_id
+                );
+            }
+        }
+    }
+}
+
+/// The timestamp of the most recent time a digest should have been sent.
+/// The hour and day are read in the timezone of `now`.
+fn last_digest_due(mode: NotificationMode, hour: u8, day: u8, now: DateTime<Tz>) -> i64 {
+    let tz = now.timezone();
+
+    let at_hour = |date: NaiveDate| {
+        let local = date.and_hms_opt(hour as u32, 0, 0).unwrap_or_default();
+        // An hour skipped by daylight saving time is moved to the next one
+        tz.from_local_datetime(&local)
+            .earliest()
+            .or_else(|| {
+                tz.from_local_datetime(&(local + ChronoDuration::hours(1)))
+                    .earliest()
+            })
+            .unwrap_or_else(|| tz.from_utc_datetime(&local))
+    };
+
+    let days_back = match mode {
+        NotificationMode::Weekly => {
+            (now.weekday().num_days_from_monday() as i64 - day as i64).rem_euclid(7)
+        }
+        _ => 0,
+    };
+
+    let mut date = now.date_naive() - ChronoDuration::days(days_back);
+
+    if at_hour(date) > now {
+        date -= match mode {
+            NotificationMode::Weekly => ChronoDuration::weeks(1),
+            _ => ChronoDuration::days(1),
+        };
+    }
+
+    at_hour(date).timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(tz: Tz, y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Tz> {
+        tz.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn daily_digests_are_due_at_the_latest_past_hour() {
+        // A wednesday
+        let now = at(chrono_tz::UTC, 2024, 1, 10, 15, 30);
+
+        assert_eq!(
+            last_digest_due(NotificationMode::Daily, 9, 0, now),
+            at(chrono_tz::UTC, 2024, 1, 10, 9, 0).timestamp()
+        );
+        assert_eq!(
+            last_digest_due(NotificationMode::Daily, 18, 0, now),
+            at(chrono_tz::UTC, 2024, 1, 9, 18, 0).timestamp()
+        );
+    }
+
+    #[test]
+    fn weekly_digests_are_due_on_the_latest_past_day() {
+        let now = at(chrono_tz::UTC, 2024, 1, 10, 15, 30);
+
+        // Monday
+        assert_eq!(
+            last_digest_due(NotificationMode::Weekly, 9, 0, now),
+            at(chrono_tz::UTC, 2024, 1, 8, 9, 0).timestamp()
+        );
+        // Wednesday, but later than now
+        assert_eq!(
+            last_digest_due(NotificationMode::Weekly, 18, 2, now),
+            at(chrono_tz::UTC, 2024, 1, 3, 18, 0).timestamp()
+        );
+    }
+
+    #[test]
+    fn digest_hours_are_read_in_the_timezone_of_the_guild() {
+        let berlin = chrono_tz::Europe::Berlin;
+        let now = at(berlin, 2024, 1, 10, 15, 30);
+
+        assert_eq!(
+            last_digest_due(NotificationMode::Daily, 9, 0, now),
+            at(chrono_tz::UTC, 2024, 1, 10, 8, 0).timestamp()
+        );
+
+        // 2:00 doesn't exist on the day clocks are put forward
+        let now = at(berlin, 2024, 3, 31, 12, 0);
+        assert_eq!(
+            last_digest_due(NotificationMode::Daily, 2, 0, now),
+            at(berlin, 2024, 3, 31, 3, 0).timestamp()
+        );
+    }
+}
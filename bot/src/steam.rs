@@ -72,11 +72,18 @@ async fn get_item_from_steam(item_id: u64) -> Result<ItemInfo, Error> {
         None
     };
 
+    // Steam sends the size as a string, so we parse it ourselves
+    let file_size = parse["response"]["publishedfiledetails"][0]["file_size"]
+        .to_string()
+        .parse()
+        .ok();
+
     Ok(ItemInfo {
         id: item_id,
         name,
         last_updated,
         preview_url,
+        file_size,
     })
 }
 
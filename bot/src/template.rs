@@ -0,0 +1,201 @@
+use crate::db::ItemInfo;
+
+/// Placeholders that can be used in the message above the embeds
+pub const HEADER_PLACEHOLDERS: &[&str] = &["count"];
+
+/// Placeholders that can be used in the embed of every item
+pub const ITEM_PLACEHOLDERS: &[&str] =
+    &["name", "id", "url", "note", "updated_at", "size", "count"];
+
+pub const MAX_TEMPLATE_LENGTH: usize = 1000;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum TemplateKind {
+    #[name = "Message"]
+    #[name_localized("de", "Nachricht")]
+    Header,
+    #[name = "Item embed"]
+    #[name_localized("de", "Eintrags-Embed")]
+    Item,
+}
+
+impl TemplateKind {
+    pub fn placeholders(&self) -> &'static [&'static str] {
+        match self {
+            TemplateKind::Header => HEADER_PLACEHOLDERS,
+            TemplateKind::Item => ITEM_PLACEHOLDERS,
+        }
+    }
+}
+
+/// The templates a guild configured, `None` means the default layout is used
+#[derive(Debug, Clone, Default)]
+pub struct Templates {
+    pub header: Option<String>,
+    pub item: Option<String>,
+}
+
+impl Templates {
+    pub fn header(&self, default: &str, count: usize) -> String {
+        match &self.header {
+            Some(header) => render(header, &[("count", count.to_string())]),
+            None => default.to_string(),
+        }
+    }
+}
+
+/// Checks that every placeholder is known and every brace is closed.
+/// Use `{{` and `}}` for literal braces.
+pub fn validate(template: &str, allowed: &[&str]) -> Result<(), String> {
+    if template.chars().count() > MAX_TEMPLATE_LENGTH {
+        return Err(format!(
+            "The template may be at most {} characters long.",
+            MAX_TEMPLATE_LENGTH
+        ));
+    }
+
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') | None => return Err("Unclosed `{` in the template.".to_string()),
+                        Some(c) => name.push(c),
+                    }
+                }
+
+                if !allowed.contains(&name.as_str()) {
+                    return Err(format!(
+                        "Unknown placeholder `{{{}}}`. Available are: {}",
+                        name,
+                        allowed
+                            .iter()
+                            .map(|p| format!("`{{{}}}`", p))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+            }
+            '}' => return Err("Unmatched `}` in the template.".to_string()),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces the placeholders with their values. The template has to be validated first
+pub fn render(template: &str, values: &[(&str, String)]) -> String {
+    let mut res = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                res.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                res.push('}');
+            }
+            '{' => {
+                let name: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                if let Some((_, value)) = values.iter().find(|(k, _)| *k == name) {
+                    res.push_str(value);
+                }
+            }
+            c => res.push(c),
+        }
+    }
+
+    res
+}
+
+pub fn item_values(
+    item_info: &ItemInfo,
+    note: &Option<String>,
+    count: usize,
+) -> Vec<(&'static str, String)> {
+    vec![
+        ("name", item_info.name.clone()),
+        ("id", item_info.id.to_string()),
+        (
+            "url",
+            format!(
+                "https://steamcommunity.com/sharedfiles/filedetails/?id={}",
+                item_info.id
+            ),
+        ),
+        ("note", note.clone().unwrap_or_default()),
+        ("updated_at", format!("<t:{}:f>", item_info.last_updated)),
+        (
+            "size",
+            item_info
+                .file_size
+                .map(format_size)
+                .unwrap_or_else(|| "unknown".to_string()),
+        ),
+        ("count", count.to_string()),
+    ]
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_fills_placeholders_and_keeps_escaped_braces() {
+        let values = [("name", "Map".to_string()), ("count", "2".to_string())];
+
+        assert_eq!(
+            render("{{{name}}} updated {count} times", &values),
+            "{Map} updated 2 times"
+        );
+    }
+
+    #[test]
+    fn validate_rejects_unknown_and_unclosed_placeholders() {
+        assert!(validate("{name} {url}", ITEM_PLACEHOLDERS).is_ok());
+        assert!(validate("{{name}}", HEADER_PLACEHOLDERS).is_ok());
+        assert!(validate("{name}", HEADER_PLACEHOLDERS).is_err());
+        assert!(validate("{name", ITEM_PLACEHOLDERS).is_err());
+        assert!(validate("name}", ITEM_PLACEHOLDERS).is_err());
+        assert!(validate(&"x".repeat(MAX_TEMPLATE_LENGTH + 1), ITEM_PLACEHOLDERS).is_err());
+    }
+
+    #[test]
+    fn sizes_use_the_largest_fitting_unit() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(1048576), "1.0 MB");
+    }
+}
@@ -1,30 +1,61 @@
-CREATE TABLE Servers (
+CREATE TABLE IF NOT EXISTS Servers (
 	ServerId BIGINT NOT NULL,
 	ChannelId BIGINT NULL,
 	Schedule INT NULL,
 	LastUpdate BIGINT NULL,
+	MentionRole BIGINT NULL,
+	MentionUser BIGINT NULL,
+	WebhookUrl VARCHAR(2083) NULL,
+	WebhookName varchar(80) NULL,
+	WebhookAvatar VARCHAR(2083) NULL,
+	HookUrl VARCHAR(2083) NULL,
+	HookSecret varchar(128) NULL,
+	NotificationMode varchar(10) DEFAULT 'immediate' NOT NULL,
+	DigestHour TINYINT NULL,
+	DigestDay TINYINT NULL,
+	LastDigest BIGINT NULL,
+	HeaderTemplate varchar(1000) NULL,
+	ItemTemplate varchar(1000) NULL,
+	LastRestart BIGINT NULL,
+	Timezone varchar(64) NULL,
+	Language varchar(8) NULL,
+	ManagerRole BIGINT NULL,
+	ContributorRole BIGINT NULL,
 	CONSTRAINT Servers_PK PRIMARY KEY (ServerId)
 )
 ENGINE=InnoDB
 DEFAULT CHARSET=utf8mb4
 COLLATE=utf8mb4_0900_ai_ci;
 
-CREATE TABLE Items (
+CREATE TABLE IF NOT EXISTS Items (
 	ItemId BIGINT NOT NULL,
 	ItemName varchar(100) NOT NULL,
 	LastUpdate BIGINT NOT NULL,
     PreviewUrl VARCHAR(2083) NULL,
+    FileSize BIGINT NULL,
 	CONSTRAINT Items_PK PRIMARY KEY (ItemId)
 )
 ENGINE=InnoDB
 DEFAULT CHARSET=utf8mb4
 COLLATE=utf8mb4_0900_ai_ci;
 
-CREATE TABLE Subscriptions (
+CREATE TABLE IF NOT EXISTS Subscriptions (
 	ServerId BIGINT NOT NULL,
 	ItemId BIGINT NOT NULL,
 	LastUpdate BIGINT NOT NULL,
-	Note varchar(500) NULL,
+	Note TEXT NULL,
+	MentionRole BIGINT NULL,
+	MentionUser BIGINT NULL,
+	DeployedVersion BIGINT NULL,
+	Muted BOOLEAN DEFAULT FALSE NOT NULL,
+	MutedUntil BIGINT NULL,
+	CheckInterval INT NULL,
+	LastChecked BIGINT NULL,
+	AddedAt BIGINT NULL,
+	AddedBy BIGINT NULL,
+	AddedFrom BIGINT NULL,
+	RemovedAt BIGINT NULL,
+	RemovalBatch BIGINT NULL,
 	CONSTRAINT Subscriptions_PK PRIMARY KEY (ServerId,ItemId),
 	CONSTRAINT Subscriptions_FK FOREIGN KEY (ItemId) REFERENCES Items(ItemId) ON DELETE CASCADE ON UPDATE CASCADE,
 	CONSTRAINT Subscriptions_FK_1 FOREIGN KEY (ServerId) REFERENCES Servers(ServerId) ON DELETE CASCADE ON UPDATE CASCADE
@@ -32,3 +63,138 @@ CREATE TABLE Subscriptions (
 ENGINE=InnoDB
 DEFAULT CHARSET=utf8mb4
 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE IF NOT EXISTS PendingDigests (
+	ServerId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	DetectedAt BIGINT NOT NULL,
+	CONSTRAINT PendingDigests_PK PRIMARY KEY (ServerId,ItemId,DetectedAt),
+	CONSTRAINT PendingDigests_FK FOREIGN KEY (ItemId) REFERENCES Items(ItemId) ON DELETE CASCADE ON UPDATE CASCADE,
+	CONSTRAINT PendingDigests_FK_1 FOREIGN KEY (ServerId) REFERENCES Servers(ServerId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE IF NOT EXISTS Watchers (
+	UserId BIGINT NOT NULL,
+	DmClosed BOOLEAN DEFAULT FALSE NOT NULL,
+	Language varchar(8) NULL,
+	CONSTRAINT Watchers_PK PRIMARY KEY (UserId)
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE IF NOT EXISTS Watches (
+	UserId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	LastNotified BIGINT NOT NULL,
+	CONSTRAINT Watches_PK PRIMARY KEY (UserId,ItemId),
+	CONSTRAINT Watches_FK FOREIGN KEY (ItemId) REFERENCES Items(ItemId) ON DELETE CASCADE ON UPDATE CASCADE,
+	CONSTRAINT Watches_FK_1 FOREIGN KEY (UserId) REFERENCES Watchers(UserId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE IF NOT EXISTS UpdateEvents (
+	ServerId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	Version BIGINT NOT NULL,
+	PostedAt BIGINT NOT NULL,
+	Status varchar(10) DEFAULT 'pending' NOT NULL,
+	HandledBy BIGINT NULL,
+	HandledAt BIGINT NULL,
+	SnoozedUntil BIGINT NULL,
+	CONSTRAINT UpdateEvents_PK PRIMARY KEY (ServerId,ItemId,Version),
+	CONSTRAINT UpdateEvents_FK FOREIGN KEY (ItemId) REFERENCES Items(ItemId) ON DELETE CASCADE ON UPDATE CASCADE,
+	CONSTRAINT UpdateEvents_FK_1 FOREIGN KEY (ServerId) REFERENCES Servers(ServerId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE IF NOT EXISTS ItemGroups (
+	GroupId BIGINT NOT NULL AUTO_INCREMENT,
+	ServerId BIGINT NOT NULL,
+	Name varchar(100) NOT NULL,
+	MentionRole BIGINT NULL,
+	MentionUser BIGINT NULL,
+	CONSTRAINT ItemGroups_PK PRIMARY KEY (GroupId),
+	CONSTRAINT ItemGroups_UN UNIQUE KEY (ServerId,Name),
+	CONSTRAINT ItemGroups_FK FOREIGN KEY (ServerId) REFERENCES Servers(ServerId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE IF NOT EXISTS GroupMembers (
+	GroupId BIGINT NOT NULL,
+	ServerId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	CONSTRAINT GroupMembers_PK PRIMARY KEY (GroupId,ItemId),
+	CONSTRAINT GroupMembers_FK FOREIGN KEY (GroupId) REFERENCES ItemGroups(GroupId) ON DELETE CASCADE ON UPDATE CASCADE,
+	CONSTRAINT GroupMembers_FK_1 FOREIGN KEY (ServerId,ItemId) REFERENCES Subscriptions(ServerId,ItemId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE IF NOT EXISTS EscalationRules (
+	ServerId BIGINT NOT NULL,
+	Hours INT NOT NULL,
+	RoleId BIGINT NOT NULL,
+	GroupId BIGINT NULL,
+	CONSTRAINT EscalationRules_PK PRIMARY KEY (ServerId),
+	CONSTRAINT EscalationRules_FK FOREIGN KEY (ServerId) REFERENCES Servers(ServerId) ON DELETE CASCADE ON UPDATE CASCADE,
+	CONSTRAINT EscalationRules_FK_1 FOREIGN KEY (GroupId) REFERENCES ItemGroups(GroupId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE IF NOT EXISTS Reminders (
+	ServerId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	Version BIGINT NOT NULL,
+	RemindAt BIGINT NOT NULL,
+	CONSTRAINT Reminders_PK PRIMARY KEY (ServerId,ItemId,Version),
+	CONSTRAINT Reminders_FK FOREIGN KEY (ServerId, ItemId, Version) REFERENCES UpdateEvents(ServerId, ItemId, Version) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE IF NOT EXISTS AuditLog (
+	AuditId BIGINT NOT NULL AUTO_INCREMENT,
+	ServerId BIGINT NOT NULL,
+	UserId BIGINT NOT NULL,
+	Command varchar(32) NOT NULL,
+	ItemId BIGINT NULL,
+	Field varchar(32) NOT NULL,
+	OldValue varchar(500) NULL,
+	NewValue varchar(500) NULL,
+	CreatedAt BIGINT NOT NULL,
+	CONSTRAINT AuditLog_PK PRIMARY KEY (AuditId),
+	CONSTRAINT AuditLog_FK FOREIGN KEY (ServerId) REFERENCES Servers(ServerId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE IF NOT EXISTS Notes (
+	NoteId BIGINT NOT NULL AUTO_INCREMENT,
+	ServerId BIGINT NOT NULL,
+	ItemId BIGINT NOT NULL,
+	Content varchar(500) NOT NULL,
+	AuthorId BIGINT NULL,
+	CreatedAt BIGINT NOT NULL,
+	RemovedBy BIGINT NULL,
+	RemovedAt BIGINT NULL,
+	CONSTRAINT Notes_PK PRIMARY KEY (NoteId),
+	CONSTRAINT Notes_FK FOREIGN KEY (ServerId,ItemId) REFERENCES Subscriptions(ServerId,ItemId) ON DELETE CASCADE ON UPDATE CASCADE
+)
+ENGINE=InnoDB
+DEFAULT CHARSET=utf8mb4
+COLLATE=utf8mb4_0900_ai_ci;